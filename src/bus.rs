@@ -0,0 +1,74 @@
+/*
+    the cpu only ever talks to the outside world through a bus.
+    every read and write goes through it, so ram, rom and
+    memory-mapped devices can be placed anywhere in the 64K address space
+*/
+
+/// something the cpu can read from and write to
+pub trait Bus {
+    /// read a byte from address
+    fn read(&mut self, address: u16) -> u8;
+
+    /// write a byte to address
+    fn write(&mut self, address: u16, data: u8);
+
+    /// the byte at address without any side effects, for debug output
+    /// None if it cannot be known without a real read, as with most i/o registers
+    fn peek(&self, _address: u16) -> Option<u8> {
+        None
+    }
+
+    /// the bank shown by every banked window, for debug output
+    fn bank_selection(&self) -> Vec<BankSelection> {
+        Vec::new()
+    }
+}
+
+/// which bank a window of the address space currently shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BankSelection {
+    /// first address of the window
+    pub start: u16,
+    /// last address of the window
+    pub end: u16,
+    /// selected bank
+    pub bank: usize,
+    /// number of banks the window can show
+    pub count: usize,
+}
+
+impl<B: Bus + ?Sized> Bus for Box<B> {
+    fn read(&mut self, address: u16) -> u8 {
+        (**self).read(address)
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        (**self).write(address, data)
+    }
+
+    fn peek(&self, address: u16) -> Option<u8> {
+        (**self).peek(address)
+    }
+
+    fn bank_selection(&self) -> Vec<BankSelection> {
+        (**self).bank_selection()
+    }
+}
+
+impl<B: Bus + ?Sized> Bus for &mut B {
+    fn read(&mut self, address: u16) -> u8 {
+        (**self).read(address)
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        (**self).write(address, data)
+    }
+
+    fn peek(&self, address: u16) -> Option<u8> {
+        (**self).peek(address)
+    }
+
+    fn bank_selection(&self) -> Vec<BankSelection> {
+        (**self).bank_selection()
+    }
+}
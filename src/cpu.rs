@@ -1,15 +1,139 @@
 use crate::{
-    mem::{self, Memory},
+    bus::Bus,
+    error::{CpuError, IllegalOpcodePolicy, LoadError},
+    io_port::{IoPort, PORT_ADDRESS},
+    mem::Memory,
     proc_stat::ProcStat,
-    op_codes::*
+    op_codes::*,
+    op_table::AddrMode,
+    variant::Variant,
 };
 
+/// the constant an unstable opcode ORs into the accumulator before using it,
+/// it differs from chip to chip and with temperature, 0xEE is the common value
+const UNSTABLE_MAGIC: u8 = 0xEE;
+
+/// address of the vector used by NMI
+pub const NMI_VECTOR: u16 = 0xFFFA;
+/// cycles taken by the reset, irq and nmi sequences
+pub const INTERRUPT_CYCLES: u8 = 7;
+
+/// number of addresses the cpu can reach
+const ADDRESS_SPACE: usize = 0x10000;
+
+/// base address of the stack, which always lives in page one
+pub const STACK_BASE: u16 = 0x0100;
+
+/// address of the vector read on reset
+pub const RESET_VECTOR: u16 = 0xFFFC;
+/// address of the vector used by BRK and IRQ
+pub const IRQ_VECTOR: u16 = 0xFFFE;
+
+/// the stack pointer wrapped around page one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackFault {
+    /// a push wrapped from 0x0100 to 0x01FF
+    Overflow { pc: u16 },
+    /// a pull wrapped from 0x01FF to 0x0100
+    Underflow { pc: u16 },
+}
+
+/// what a call to `Cpu::step` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// an instruction was executed
+    Instruction { pc: u16, opcode: u8, cycles: u8 },
+    /// an interrupt sequence was entered through vector
+    Interrupt { vector: u16, cycles: u8 },
+    /// the cpu is waiting for an interrupt after WAI,
+    /// nothing runs until irq or nmi is asserted
+    Waiting,
+    /// the cpu was stopped by STP, nothing runs until reset
+    Stopped,
+}
+
+impl Step {
+    /// number of cycles the step took, none while waiting or stopped
+    /// so the host decides how much time passes
+    pub fn cycles(&self) -> u8 {
+        match *self {
+            Step::Instruction { cycles, .. } | Step::Interrupt { cycles, .. } => cycles,
+            Step::Waiting | Step::Stopped => 0,
+        }
+    }
+}
+
+/// a single bus access, the 6502 makes exactly one every cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// data was read from address
+    Read { address: u16, data: u8 },
+    /// data was written to address
+    Write { address: u16, data: u8 },
+}
+
+/// everything an instruction can change, so `tick` can run it again from the start
+#[derive(Debug, Clone, Copy)]
+struct Snapshot {
+    pc: u16,
+    sp: u8,
+    a: u8,
+    x: u8,
+    y: u8,
+    p: ProcStat,
+    cycles: u64,
+    extra_cycles: u8,
+    irq_line: bool,
+    nmi_pending: bool,
+    delayed_i: Option<bool>,
+    page_crossed: bool,
+    jammed: Option<CpuError>,
+    waiting: bool,
+    stopped: bool,
+    direction: u8,
+    data: u8,
+}
+
+/// an instruction being run one bus cycle at a time
+/// every tick runs it again from its snapshot: accesses made on earlier ticks
+/// are replayed from the log, one new access reaches the bus and any after
+/// that are dropped, until a pass gets to the end of the instruction
 #[derive(Debug, Default, Clone)]
-pub struct Cpu {
+struct Ticker {
+    /// the cpu before the instruction, None between instructions
+    start: Option<Snapshot>,
+    /// accesses the instruction has already made on the bus
+    accesses: Vec<Access>,
+    /// accesses made by the current pass, replayed or not
+    count: usize,
+    /// how many accesses the current pass may make before they are dropped
+    limit: usize,
+    /// a pass is running
+    running: bool,
+    /// the last access reached the bus
+    live: bool,
+    /// cycles left of a finished instruction that make no modelled access
+    idle: u8,
+}
+
+/// what happens to an access made by a pass of `tick`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    /// it reaches the bus
+    Bus,
+    /// an earlier tick made it, this is the data it saw
+    Replay(u8),
+    /// it belongs to a later tick
+    Drop,
+}
+
+/// a 6502 attached to a bus, by default 64K of flat ram
+#[derive(Debug, Clone)]
+pub struct Cpu<B = Memory> {
     /// program counter
     pub pc: u16,      
-    /// stack pointer      
-    pub sp: u16,  
+    /// stack pointer, an offset into page one      
+    pub sp: u8,  
     /// accumulator          
     pub a: u8,         
     /// x register     
@@ -18,156 +142,1058 @@ pub struct Cpu {
     pub y: u8,          
     /// processor status    
     pub p: ProcStat,        
+    /// total cycles executed since power on
+    pub cycles: u64,
+    /// cycles the last instruction took on top of its base cost
+    /// (taken branches, page crossings)
+    pub extra_cycles: u8,
+    /// reproduce the nmos JMP (ind) bug, where a vector at $xxFF
+    /// takes its high byte from $xx00 instead of crossing the page.
+    /// set for the nmos variants
+    pub jmp_indirect_bug: bool,
+    /// called with the bus and the new pin levels whenever the 6510 i/o port changes,
+    /// and on reset, so the host can bank memory in and out
+    pub on_port_change: Option<fn(&mut B, u8)>,
+    /// called with the bus when the stack wraps around page one,
+    /// real software rarely does this on purpose so it is usually a bug
+    pub on_stack_fault: Option<fn(&mut B, StackFault)>,
+    /// what to do with opcodes that are not documented instructions
+    pub illegal_opcodes: IllegalOpcodePolicy,
+    /// which chip is emulated, fixed at construction
+    variant: Variant,
+    /// issue the dummy reads the nmos 6502 makes on cycles that do no useful work,
+    /// so the bus sees every instruction's exact sequence of accesses.
+    /// only devices with read side effects can tell the difference
+    pub cycle_accurate: bool,
+
+    /// level of the irq input, held until released
+    irq_line: bool,
+    /// level of the nmi input
+    nmi_line: bool,
+    /// set on a falling edge of nmi, cleared once serviced
+    nmi_pending: bool,
+    /// I as it was before CLI, SEI or PLP changed it, which the next
+    /// interrupt poll still sees
+    delayed_i: Option<bool>,
+    /// the last indexed address crossed a page
+    page_crossed: bool,
+    /// set once a KIL opcode locks up the cpu, until reset
+    jammed: Option<CpuError>,
+    /// the 6510 i/o port, unused on other variants
+    port: IoPort,
+    /// WAI was executed and no interrupt has arrived since
+    waiting: bool,
+    /// STP was executed, until reset
+    stopped: bool,
+    /// the instruction `tick` is part way through
+    tick: Ticker,
+
+    /// memory module, every read and write goes through it
+    pub mem: B,    
+}
 
-    /// memory module
-    pub mem: Memory,    
+impl<B: Bus + Default> Default for Cpu<B> {
+    fn default() -> Self {
+        Self::with_bus(B::default())
+    }
 }
 
 impl Cpu {
-    /// create a new cpu
+    /// create a new cpu with 64K of ram
     pub fn new() -> Self {
-        Self::default()
+        Self::with_bus(Memory::default())
+    }
+}
+
+impl<B: Bus> Cpu<B> {
+    /// create a new nmos 6502 attached to bus
+    pub fn with_bus(bus: B) -> Self {
+        Self::with_variant(Variant::default(), bus)
+    }
+
+    /// create a new cpu of the given variant attached to bus
+    pub fn with_variant(variant: Variant, bus: B) -> Self {
+        Cpu {
+            pc: 0,
+            sp: 0,
+            a: 0,
+            x: 0,
+            y: 0,
+            p: ProcStat::default(),
+            cycles: 0,
+            extra_cycles: 0,
+            jmp_indirect_bug: !variant.is_cmos(),
+            on_port_change: None,
+            on_stack_fault: None,
+            illegal_opcodes: IllegalOpcodePolicy::default(),
+            variant,
+            cycle_accurate: false,
+            irq_line: false,
+            nmi_line: false,
+            nmi_pending: false,
+            delayed_i: None,
+            page_crossed: false,
+            jammed: None,
+            port: IoPort::default(),
+            waiting: false,
+            stopped: false,
+            tick: Ticker::default(),
+            mem: bus,
+        }
+    }
+
+    /// the chip being emulated
+    pub fn variant(&self) -> Variant {
+        self.variant
     }
 
-    /// reset cpu to initial state
-    /// an optional address can be passed to set the program counter
-    /// if no address is passed, the program counter is set to 0xFFFC
-    /// this is the default reset vector for the NES
-    /// https://wiki.nesdev.com/w/index.php/CPU_power_up_state
-    pub fn reset(&mut self, address: Option<u16>) -> Self {
-        self.pc = 0xFFFC;
-        self.sp = 0x0100;
-        self.a = 0;
-        self.x = 0;
-        self.y = 0;
-        self.p.clear();
+    /// the 6510 i/o port
+    pub fn port(&self) -> &IoPort {
+        &self.port
+    }
+
+    /// drive the pins of the 6510 i/o port that are set as inputs
+    pub fn set_port_input(&mut self, levels: u8) {
+        let pins = self.port.pins();
+        self.port.input = levels;
+        if self.port.pins() != pins {
+            self.port_changed();
+        }
+    }
 
-        // read 0xFFFC and 0xFFFD and
-        // jump to that address for instructions
-        if let Some(address) = address {
-            self.mem.write_word(self.pc as usize, address);
-            self.pc = self.mem.read_word(0xFFFC);
+    /// reset the cpu the way the RES line does
+    /// the stack pointer is decremented by three phantom pushes that never write,
+    /// interrupts are disabled and the pc is loaded from the reset vector at 0xFFFC.
+    /// the 65c02 parts also leave decimal mode, the nmos ones keep D as it was.
+    /// a, x, y and memory are left untouched
+    /// https://www.nesdev.org/wiki/CPU_power_up_state
+    pub fn reset(&mut self) {
+        self.dummy_read(self.pc);
+        self.dummy_read(self.pc);
+        for _ in 0..3 {
+            self.read(STACK_BASE | self.sp as u16);
+            self.sp = self.sp.wrapping_sub(1);
         }
 
-        self.to_owned()
+        self.p.insert(ProcStat::I);
+        if self.variant.is_cmos() {
+            self.p.remove(ProcStat::D);
+        }
+        self.nmi_pending = false;
+        self.delayed_i = None;
+        self.jammed = None;
+        self.waiting = false;
+        self.stopped = false;
+        // an instruction half way through `tick` is abandoned
+        self.tick = Ticker::default();
+        if self.variant.has_io_port() {
+            // every pin becomes an input, the host sees the pulled up levels
+            self.port.direction = 0;
+            self.port.data = 0;
+            self.port_changed();
+        }
+        self.pc = self.read_word(RESET_VECTOR);
+        self.cycles += INTERRUPT_CYCLES as u64;
+    }
+
+    /// reset the cpu, then start executing at pc instead of the reset vector
+    pub fn reset_to(&mut self, pc: u16) {
+        self.reset();
+        self.pc = pc;
     }
 
     /// load a program into the cpu's memory at a given address
-    pub fn load_program(&mut self, address: usize, program: Vec<u8>) {
-        todo!()
+    /// nothing is written if the program would run past 0xFFFF
+    /// the bytes go straight to the bus, so the target must be RAM, a byte that
+    /// does not read back (ROM, a device, open bus) stops the load with `NotWritable`
+    /// on the 6510 the bytes at 0x0000 and 0x0001 go to the ram under the i/o port,
+    /// the port itself and `on_port_change` are left alone
+    pub fn load_program(&mut self, address: u16, program: &[u8]) -> Result<(), LoadError> {
+        Self::check_segment(address, program)?;
+        for (offset, byte) in program.iter().enumerate() {
+            self.load_byte(address + offset as u16, *byte)?;
+        }
+        Ok(())
+    }
+
+    /// load several (address, bytes) segments, and point the reset vector at entry if given
+    /// every segment is checked against the address space before anything is written,
+    /// like `load_program` the segments and the vector must land in RAM
+    pub fn load_segments(
+        &mut self,
+        segments: &[(u16, &[u8])],
+        entry: Option<u16>,
+    ) -> Result<(), LoadError> {
+        for (address, bytes) in segments {
+            Self::check_segment(*address, bytes)?;
+        }
+
+        for (address, bytes) in segments {
+            self.load_program(*address, bytes)?;
+        }
+
+        if let Some(entry) = entry {
+            self.load_byte(RESET_VECTOR, entry as u8)?;
+            self.load_byte(RESET_VECTOR + 1, (entry >> 8) as u8)?;
+        }
+        Ok(())
+    }
+
+    /// make sure bytes loaded at address stay inside the 64k address space
+    fn check_segment(address: u16, bytes: &[u8]) -> Result<(), LoadError> {
+        if address as usize + bytes.len() > ADDRESS_SPACE {
+            return Err(LoadError::OutOfBounds {
+                address,
+                len: bytes.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// write one byte of an image and check that it stuck
+    fn load_byte(&mut self, address: u16, byte: u8) -> Result<(), LoadError> {
+        self.mem.write(address, byte);
+        if self.mem.peek(address) != Some(byte) {
+            return Err(LoadError::NotWritable { address });
+        }
+        Ok(())
     }
 
     /// print contents of registers, pc, sp, and status flags and current instruction
     /// useful when the emulator crashes, you can get a state of the machine
-    pub fn debug_print(&mut self) {
+    pub fn debug_print(&self) {
         println!("pc: 0x{:04x}", self.pc);
-        println!("sp: 0x{:04x}", self.sp);
+        println!("sp: 0x{:04x}", STACK_BASE | self.sp as u16);
         println!("a : 0x{:04x}", self.a);
         println!("x : 0x{:04x}", self.x);
         println!("y : 0x{:04x}", self.y);
         println!("ps: {}", self.p);
-        println!(
-            "current instruction: 0x{:02X}",
-            self.mem.read_byte(self.pc as usize)
-        );
+        if self.variant.has_io_port() {
+            println!(
+                "io: ddr 0x{:02x} port 0x{:02x}",
+                self.port.direction, self.port.data
+            );
+        }
+        for window in self.mem.bank_selection() {
+            println!(
+                "bank 0x{:04x}-0x{:04x}: {} of {}",
+                window.start, window.end, window.bank, window.count
+            );
+        }
+        // peek rather than read so printing never disturbs a device
+        let instruction = if self.variant.has_io_port() && self.pc <= PORT_ADDRESS {
+            Some(self.port.read(self.pc))
+        } else {
+            self.mem.peek(self.pc)
+        };
+        match instruction {
+            Some(opcode) => println!(
+                "current instruction: 0x{:02X} ({})",
+                opcode,
+                self.variant.opcodes()[opcode as usize]
+            ),
+            None => println!("current instruction: ??"),
+        }
+    }
+
+    /// assert the level-sensitive irq line
+    /// it is serviced at every instruction boundary until released, unless I is set
+    pub fn assert_irq(&mut self) {
+        self.irq_line = true;
+    }
+
+    /// release the irq line
+    pub fn release_irq(&mut self) {
+        self.irq_line = false;
+    }
+
+    /// assert the edge-triggered nmi line
+    /// only the transition is latched, so the line must be released
+    /// before another nmi can be raised
+    pub fn assert_nmi(&mut self) {
+        if !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = true;
+    }
+
+    /// release the nmi line
+    pub fn release_nmi(&mut self) {
+        self.nmi_line = false;
+    }
+
+    /// run whole instructions until at least the given number of cycles have passed
+    /// returns the number of cycles actually run, which can overshoot by part of an instruction.
+    /// stops early if the cpu waits for an interrupt or is stopped
+    pub fn run_for_cycles(&mut self, cycles: u64) -> Result<u64, CpuError> {
+        let mut ran = 0;
+        while ran < cycles {
+            match self.step()? {
+                Step::Waiting | Step::Stopped => break,
+                step => ran += step.cycles() as u64,
+            }
+        }
+        Ok(ran)
+    }
+
+    /// run instructions until predicate returns true, it is checked before every step
+    /// returns the number of cycles run.
+    /// stops early if the cpu waits for an interrupt or is stopped
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<u64, CpuError>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        let mut ran = 0;
+        while !predicate(self) {
+            match self.step()? {
+                Step::Waiting | Step::Stopped => break,
+                step => ran += step.cycles() as u64,
+            }
+        }
+        Ok(ran)
+    }
+
+    /// run a single bus cycle, returning the access made on it
+    /// accesses come one per call in the order the hardware makes them, dummy
+    /// accesses included as if `cycle_accurate` were set. registers and the cycle
+    /// counter keep their old values until the last access of an instruction.
+    /// None is returned for cycles whose access is not modelled: illegal opcodes
+    /// skipped by `IllegalOpcodePolicy::Nop`, the extra decimal mode cycle of the
+    /// 65c02 and some of its reserved no-ops, and every call while the cpu is
+    /// waiting or stopped.
+    /// dummy accesses follow the nmos pattern on every variant
+    pub fn tick(&mut self) -> Result<Option<Access>, CpuError> {
+        if self.tick.idle > 0 {
+            self.tick.idle -= 1;
+            return Ok(None);
+        }
+        if self.tick.start.is_none() {
+            self.tick.start = Some(self.snapshot());
+            self.tick.accesses.clear();
+        }
+
+        let made = self.tick.accesses.len();
+        if let Some(step) = self.replay(made + 1) {
+            // this tick was one of the instruction's cycles even if it made no access
+            let accesses = self.tick.accesses.len().max(1);
+            self.tick.idle = (step?.cycles() as usize).saturating_sub(accesses) as u8;
+        }
+        Ok(self.tick.accesses.get(made).copied())
+    }
+
+    /// execute a single instruction, or enter a pending interrupt
+    /// an instruction part way through `tick` is finished rather than started again.
+    /// a jammed cpu keeps returning the same error until it is reset
+    pub fn step(&mut self) -> Result<Step, CpuError> {
+        self.tick.idle = 0;
+        if self.tick.start.is_some() {
+            // with no limit the pass always reaches the end of the instruction
+            if let Some(step) = self.replay(usize::MAX) {
+                return step;
+            }
+        }
+        self.run_step()
+    }
+
+    /// run the instruction `tick` started from its snapshot, letting at most
+    /// limit accesses reach the bus or the log.
+    /// returns None if it needed more, the cpu is then left as it was before the instruction
+    fn replay(&mut self, limit: usize) -> Option<Result<Step, CpuError>> {
+        let start = self.tick.start?;
+        // the host may have moved the interrupt lines since the instruction started
+        let (irq_line, nmi_pending) = (self.irq_line, self.nmi_pending);
+        self.restore(&start);
+
+        self.tick.count = 0;
+        self.tick.limit = limit;
+        self.tick.running = true;
+        let step = self.run_step();
+        self.tick.running = false;
+
+        if step.is_ok() && self.tick.count > limit {
+            self.restore(&start);
+            self.irq_line = irq_line;
+            self.nmi_pending = nmi_pending;
+            return None;
+        }
+
+        // an nmi edge that came during the instruction is still pending after it
+        self.irq_line = irq_line;
+        self.nmi_pending |= nmi_pending && !start.nmi_pending;
+        self.tick.start = None;
+        Some(step)
+    }
+
+    /// the state an instruction can change
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            pc: self.pc,
+            sp: self.sp,
+            a: self.a,
+            x: self.x,
+            y: self.y,
+            p: self.p,
+            cycles: self.cycles,
+            extra_cycles: self.extra_cycles,
+            irq_line: self.irq_line,
+            nmi_pending: self.nmi_pending,
+            delayed_i: self.delayed_i,
+            page_crossed: self.page_crossed,
+            jammed: self.jammed,
+            waiting: self.waiting,
+            stopped: self.stopped,
+            direction: self.port.direction,
+            data: self.port.data,
+        }
+    }
+
+    /// put back the state saved by `snapshot`
+    fn restore(&mut self, snapshot: &Snapshot) {
+        self.pc = snapshot.pc;
+        self.sp = snapshot.sp;
+        self.a = snapshot.a;
+        self.x = snapshot.x;
+        self.y = snapshot.y;
+        self.p = snapshot.p;
+        self.cycles = snapshot.cycles;
+        self.extra_cycles = snapshot.extra_cycles;
+        self.irq_line = snapshot.irq_line;
+        self.nmi_pending = snapshot.nmi_pending;
+        self.delayed_i = snapshot.delayed_i;
+        self.page_crossed = snapshot.page_crossed;
+        self.jammed = snapshot.jammed;
+        self.waiting = snapshot.waiting;
+        self.stopped = snapshot.stopped;
+        self.port.direction = snapshot.direction;
+        self.port.data = snapshot.data;
+    }
+
+    /// execute a single instruction, or enter a pending interrupt
+    fn run_step(&mut self) -> Result<Step, CpuError> {
+        if let Some(error) = self.jammed {
+            return Err(error);
+        }
+        if self.stopped {
+            return Ok(Step::Stopped);
+        }
+
+        // WAI ends on any interrupt, even an irq masked by I,
+        // which just lets execution carry on after the WAI
+        if self.waiting {
+            if !self.nmi_pending && !self.irq_line {
+                return Ok(Step::Waiting);
+            }
+            self.waiting = false;
+        }
+
+        // the interrupt poll happens before CLI, SEI and PLP change I,
+        // so their effect on irq is delayed by one instruction
+        let irq_masked = self
+            .delayed_i
+            .take()
+            .unwrap_or(self.p.contains(ProcStat::I));
+        if let Some(vector) = self.service_interrupts(irq_masked) {
+            self.cycles += INTERRUPT_CYCLES as u64;
+            return Ok(Step::Interrupt {
+                vector,
+                cycles: INTERRUPT_CYCLES,
+            });
+        }
+
+        let pc = self.pc;
+        let masked_before = self.p.contains(ProcStat::I);
+        let instruction = self.fetch_byte();
+        self.extra_cycles = 0;
+        self.page_crossed = false;
+
+        // single byte instructions read the next byte and throw it away,
+        // BRK is the exception that really fetches it as padding, and the
+        // single cycle no-ops of the 65c02 never get that far
+        let info = &self.variant.opcodes()[instruction as usize];
+        if matches!(info.mode, AddrMode::Implied | AddrMode::Accumulator)
+            && info.cycles > 1
+            && instruction != BRK
+        {
+            self.dummy_read(self.pc);
+        }
+
+        if !self.execute(instruction) && !self.execute_cmos(instruction) {
+            self.illegal_opcode(pc, instruction)?;
+        }
+
+        if let CLI | SEI | PLP = instruction {
+            self.delayed_i = Some(masked_before);
+        }
+
+        if info.page_penalty && self.page_crossed {
+            self.extra_cycles += 1;
+        }
+
+        let cycles = info.cycles + self.extra_cycles;
+        self.cycles += cycles as u64;
+        Ok(Step::Instruction {
+            pc,
+            opcode: instruction,
+            cycles,
+        })
+    }
+
+    /// handle an opcode at pc that is not a documented instruction
+    /// on error the pc is left on the opcode
+    fn illegal_opcode(&mut self, pc: u16, opcode: u8) -> Result<(), CpuError> {
+        let info = &self.variant.opcodes()[opcode as usize];
+
+        // the 65c02 has no illegal opcodes, the unused ones are no-ops
+        // whatever the policy is
+        if self.variant.is_cmos() && info.mnemonic == "NOP" {
+            self.nop_operand(info.mode);
+            return Ok(());
+        }
+
+        let error = match self.illegal_opcodes {
+            IllegalOpcodePolicy::Nop => {
+                self.pc = self.pc.wrapping_add(info.len as u16 - 1);
+                return Ok(());
+            }
+            IllegalOpcodePolicy::Execute if self.variant.is_cmos() => {
+                CpuError::UnknownOpcode { pc, opcode }
+            }
+            IllegalOpcodePolicy::Execute => {
+                if self.execute_undocumented(opcode) {
+                    return Ok(());
+                }
+
+                // only KIL is left, it locks up the cpu
+                let error = CpuError::Jammed { pc, opcode };
+                self.jammed = Some(error);
+                error
+            }
+            IllegalOpcodePolicy::Trap => CpuError::UnknownOpcode { pc, opcode },
+        };
+
+        self.pc = pc;
+        Err(error)
+    }
+
+    /// execute an instruction whose opcode has been fetched
+    /// returns false if the opcode is not a documented instruction
+    fn execute(&mut self, instruction: u8) -> bool {
+        match instruction {
+            LDA_IM => self.lda_im(),
+            LDA_ABS => self.lda_abs(),
+            LDA_ABSX => self.lda_absx(),
+            LDA_ABSY => self.lda_absy(),
+            LDA_ZP => self.lda_zp(),
+            LDA_ZPX => self.lda_zpx(),
+            LDA_ZPXI => self.lda_zpxi(),
+            LDA_ZPYI => self.lda_zpyi(),
+            LDX_IM => self.ldx_im(),
+            LDX_ABS => self.ldx_abs(),
+            LDX_ABSY => self.ldx_absy(),
+            LDX_ZP => self.ldx_zp(),
+            LDX_ZPY => self.ldx_zpy(),
+            LDY_IM => self.ldy_im(),
+            LDY_ABS => self.ldy_abs(),
+            LDY_ABSX => self.ldy_absx(),
+            LDY_ZP => self.ldy_zp(),
+            LDY_ZPX => self.ldy_zpx(),
+            STA_ABS => self.sta_abs(),
+            STA_ABSX => self.sta_absx(),
+            STA_ABSY => self.sta_absy(),
+            STA_ZP => self.sta_zp(),
+            STA_ZPX => self.sta_zpx(),
+            STA_ZPXI => self.sta_zpxi(),
+            STA_ZPYI => self.sta_zpyi(),
+            STX_ABS => self.stx_abs(),
+            STX_ZP => self.stx_zp(),
+            STX_ZPY => self.stx_zpy(),
+            STY_ABS => self.sty_abs(),
+            STY_ZP => self.sty_zp(),
+            STY_ZPX => self.sty_zpx(),
+            ASL_ACC => self.asl_acc(),
+            ASL_ABS => self.asl_abs(),
+            ASL_ZP => self.asl_zp(),
+            ASL_ABSX => self.asl_absx(),
+            ASL_ZPX => self.asl_zpx(),
+            LSR_ACC => self.lsr_acc(),
+            LSR_ABS => self.lsr_abs(),
+            LSR_ZP => self.lsr_zp(),
+            LSR_ABSX => self.lsr_absx(),
+            LSR_ZPX => self.lsr_zpx(),
+            ROL_ACC => self.rol_acc(),
+            ROL_ABS => self.rol_abs(),
+            ROL_ZP => self.rol_zp(),
+            ROL_ABSX => self.rol_absx(),
+            ROL_ZPX => self.rol_zpx(),
+            ROR_ACC => self.ror_acc(),
+            ROR_ABS => self.ror_abs(),
+            ROR_ZP => self.ror_zp(),
+            ROR_ABSX => self.ror_absx(),
+            ROR_ZPX => self.ror_zpx(),
+            INC_ABS => self.inc_abs(),
+            INC_ZP => self.inc_zp(),
+            INC_ABSX => self.inc_absx(),
+            INC_ZPX => self.inc_zpx(),
+            DEC_ABS => self.dec_abs(),
+            DEC_ZP => self.dec_zp(),
+            DEC_ABSX => self.dec_absx(),
+            DEC_ZPX => self.dec_zpx(),
+            INX => self.inx(),
+            INY => self.iny(),
+            DEX => self.dex(),
+            DEY => self.dey(),
+            PHA => self.pha(),
+            PHP => self.php(),
+            PLA => self.pla(),
+            PLP => self.plp(),
+            ORA_IM => self.ora_im(),
+            ORA_ABS => self.ora_abs(),
+            ORA_ABSX => self.ora_absx(),
+            ORA_ABSY => self.ora_absy(),
+            ORA_ZP => self.ora_zp(),
+            ORA_ZPX => self.ora_zpx(),
+            ORA_ZPXI => self.ora_zpxi(),
+            ORA_ZPYI => self.ora_zpyi(),
+            ANDA_IM => self.anda_im(),
+            ANDA_ABS => self.anda_abs(),
+            ANDA_ABSX => self.anda_absx(),
+            ANDA_ABSY => self.anda_absy(),
+            ANDA_ZP => self.anda_zp(),
+            ANDA_ZPX => self.anda_zpx(),
+            ANDA_ZPXI => self.anda_zpxi(),
+            ANDA_ZPYI => self.anda_zpyi(),
+            EORA_IM => self.eor_im(),
+            EORA_ABS => self.eor_abs(),
+            EORA_ABSX => self.eor_absx(),
+            EORA_ABSY => self.eor_absy(),
+            EORA_ZP => self.eor_zp(),
+            EORA_ZPX => self.eor_zpx(),
+            EORA_ZPXI => self.eor_zpxi(),
+            EORA_ZPYI => self.eor_zpyi(),
+            ADC_IM => self.adc_im(),
+            ADC_ABS => self.adc_abs(),
+            ADC_ABSX => self.adc_absx(),
+            ADC_ABSY => self.adc_absy(),
+            ADC_ZP => self.adc_zp(),
+            ADC_ZPX => self.adc_zpx(),
+            ADC_ZPXI => self.adc_zpxi(),
+            ADC_ZPYI => self.adc_zpyi(),
+            SBC_IM => self.sbc_im(),
+            SBC_ABS => self.sbc_abs(),
+            SBC_ABSX => self.sbc_absx(),
+            SBC_ABSY => self.sbc_absy(),
+            SBC_ZP => self.sbc_zp(),
+            SBC_ZPX => self.sbc_zpx(),
+            SBC_ZPXI => self.sbc_zpxi(),
+            SBC_ZPYI => self.sbc_zpyi(),
+            CMP_IM => self.cmp_im(),
+            CMP_ABS => self.cmp_abs(),
+            CMP_ABSX => self.cmp_absx(),
+            CMP_ABSY => self.cmp_absy(),
+            CMP_ZP => self.cmp_zp(),
+            CMP_ZPX => self.cmp_zpx(),
+            CMP_ZPXI => self.cmp_zpxi(),
+            CMP_ZPYI => self.cmp_zpyi(),
+            CPX_IM => self.cpx_im(),
+            CPX_ABS => self.cpx_abs(),
+            CPX_ZP => self.cpx_zp(),
+            CPY_IM => self.cpy_im(),
+            CPY_ABS => self.cpy_abs(),
+            CPY_ZP => self.cpy_zp(),
+            BIT_ABS => self.bit_abs(),
+            BIT_ZP => self.bit_zp(),
+            BPL => self.bpl(),
+            BMI => self.bmi(),
+            BVC => self.bvc(),
+            BVS => self.bvs(),
+            BCC => self.bcc(),
+            BCS => self.bcs(),
+            BNE => self.bne(),
+            BEQ => self.beq(),
+            TAX => self.tax(),
+            TAY => self.tay(),
+            TSX => self.tsx(),
+            TXA => self.txa(),
+            TXS => self.txs(),
+            TYA => self.tya(),
+            CLC => self.clc(),
+            SEC => self.sec(),
+            CLI => self.cli(),
+            SEI => self.sei(),
+            CLD => self.cld(),
+            SED => self.sed(),
+            CLV => self.clv(),
+            BRK => self.brk(),
+            RTI => self.rti(),
+            JMP_ABS => self.jmp_abs(),
+            JMP_IND => self.jmp_ind(),
+            JSR => self.jsr(),
+            RTS => self.rts(),
+            NOP => self.nop(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// execute an instruction added by the 65c02 whose opcode has been fetched
+    /// returns false on nmos variants, and for opcodes the variant does not decode
+    fn execute_cmos(&mut self, instruction: u8) -> bool {
+        if !self.variant.is_cmos() {
+            return false;
+        }
+
+        match instruction {
+            BRA => self.bra(),
+            PHX => self.phx(),
+            PHY => self.phy(),
+            PLX => self.plx(),
+            PLY => self.ply(),
+            STZ_ZP => self.stz_zp(),
+            STZ_ZPX => self.stz_zpx(),
+            STZ_ABS => self.stz_abs(),
+            STZ_ABSX => self.stz_absx(),
+            TSB_ZP => self.tsb_zp(),
+            TSB_ABS => self.tsb_abs(),
+            TRB_ZP => self.trb_zp(),
+            TRB_ABS => self.trb_abs(),
+            ORA_ZPI => self.ora_zpi(),
+            ANDA_ZPI => self.anda_zpi(),
+            EORA_ZPI => self.eor_zpi(),
+            ADC_ZPI => self.adc_zpi(),
+            STA_ZPI => self.sta_zpi(),
+            LDA_ZPI => self.lda_zpi(),
+            CMP_ZPI => self.cmp_zpi(),
+            SBC_ZPI => self.sbc_zpi(),
+            BIT_IM => self.bit_im(),
+            BIT_ZPX => self.bit_zpx(),
+            BIT_ABSX => self.bit_absx(),
+            INC_ACC => self.inc_acc(),
+            DEC_ACC => self.dec_acc(),
+            JMP_ABSXI => self.jmp_absxi(),
+            WAI if self.variant.has_wai_stp() => self.wai(),
+            STP if self.variant.has_wai_stp() => self.stp(),
+            _ if self.variant.has_bit_ops() => return self.execute_bit_ops(instruction),
+            _ => return false,
+        }
+        true
+    }
+
+    /// execute a rockwell bit instruction whose opcode has been fetched
+    /// returns false for any other opcode
+    fn execute_bit_ops(&mut self, instruction: u8) -> bool {
+        match instruction {
+            RMB0 => self.rmb(0),
+            RMB1 => self.rmb(1),
+            RMB2 => self.rmb(2),
+            RMB3 => self.rmb(3),
+            RMB4 => self.rmb(4),
+            RMB5 => self.rmb(5),
+            RMB6 => self.rmb(6),
+            RMB7 => self.rmb(7),
+            SMB0 => self.smb(0),
+            SMB1 => self.smb(1),
+            SMB2 => self.smb(2),
+            SMB3 => self.smb(3),
+            SMB4 => self.smb(4),
+            SMB5 => self.smb(5),
+            SMB6 => self.smb(6),
+            SMB7 => self.smb(7),
+            BBR0 => self.bbr(0),
+            BBR1 => self.bbr(1),
+            BBR2 => self.bbr(2),
+            BBR3 => self.bbr(3),
+            BBR4 => self.bbr(4),
+            BBR5 => self.bbr(5),
+            BBR6 => self.bbr(6),
+            BBR7 => self.bbr(7),
+            BBS0 => self.bbs(0),
+            BBS1 => self.bbs(1),
+            BBS2 => self.bbs(2),
+            BBS3 => self.bbs(3),
+            BBS4 => self.bbs(4),
+            BBS5 => self.bbs(5),
+            BBS6 => self.bbs(6),
+            BBS7 => self.bbs(7),
+            _ => return false,
+        }
+        true
+    }
+
+    /// execute an undocumented nmos opcode whose opcode has been fetched
+    /// returns false for KIL, which has no behaviour beyond locking up
+    fn execute_undocumented(&mut self, instruction: u8) -> bool {
+        match instruction {
+            SLO_ZPXI => self.slo_zpxi(),
+            SLO_ZP => self.slo_zp(),
+            SLO_ABS => self.slo_abs(),
+            SLO_ZPYI => self.slo_zpyi(),
+            SLO_ZPX => self.slo_zpx(),
+            SLO_ABSY => self.slo_absy(),
+            SLO_ABSX => self.slo_absx(),
+            RLA_ZPXI => self.rla_zpxi(),
+            RLA_ZP => self.rla_zp(),
+            RLA_ABS => self.rla_abs(),
+            RLA_ZPYI => self.rla_zpyi(),
+            RLA_ZPX => self.rla_zpx(),
+            RLA_ABSY => self.rla_absy(),
+            RLA_ABSX => self.rla_absx(),
+            SRE_ZPXI => self.sre_zpxi(),
+            SRE_ZP => self.sre_zp(),
+            SRE_ABS => self.sre_abs(),
+            SRE_ZPYI => self.sre_zpyi(),
+            SRE_ZPX => self.sre_zpx(),
+            SRE_ABSY => self.sre_absy(),
+            SRE_ABSX => self.sre_absx(),
+            RRA_ZPXI => self.rra_zpxi(),
+            RRA_ZP => self.rra_zp(),
+            RRA_ABS => self.rra_abs(),
+            RRA_ZPYI => self.rra_zpyi(),
+            RRA_ZPX => self.rra_zpx(),
+            RRA_ABSY => self.rra_absy(),
+            RRA_ABSX => self.rra_absx(),
+            DCP_ZPXI => self.dcp_zpxi(),
+            DCP_ZP => self.dcp_zp(),
+            DCP_ABS => self.dcp_abs(),
+            DCP_ZPYI => self.dcp_zpyi(),
+            DCP_ZPX => self.dcp_zpx(),
+            DCP_ABSY => self.dcp_absy(),
+            DCP_ABSX => self.dcp_absx(),
+            ISC_ZPXI => self.isc_zpxi(),
+            ISC_ZP => self.isc_zp(),
+            ISC_ABS => self.isc_abs(),
+            ISC_ZPYI => self.isc_zpyi(),
+            ISC_ZPX => self.isc_zpx(),
+            ISC_ABSY => self.isc_absy(),
+            ISC_ABSX => self.isc_absx(),
+            SAX_ZPXI => self.sax_zpxi(),
+            SAX_ZP => self.sax_zp(),
+            SAX_ABS => self.sax_abs(),
+            SAX_ZPY => self.sax_zpy(),
+            LAX_ZPXI => self.lax_zpxi(),
+            LAX_ZP => self.lax_zp(),
+            LAX_IM => self.lax_im(),
+            LAX_ABS => self.lax_abs(),
+            LAX_ZPYI => self.lax_zpyi(),
+            LAX_ZPY => self.lax_zpy(),
+            LAX_ABSY => self.lax_absy(),
+            ANC_IM | ANC_IM_ALT => self.anc_im(),
+            ALR_IM => self.alr_im(),
+            ARR_IM => self.arr_im(),
+            XAA_IM => self.xaa_im(),
+            AXS_IM => self.axs_im(),
+            SBC_IM_ALT => self.sbc_im(),
+            LAS_ABSY => self.las_absy(),
+            TAS_ABSY => self.tas_absy(),
+            SHY_ABSX => self.shy_absx(),
+            SHX_ABSY => self.shx_absy(),
+            AHX_ZPYI => self.ahx_zpyi(),
+            AHX_ABSY => self.ahx_absy(),
+            _ if self.variant.opcodes()[instruction as usize].mnemonic == "NOP" => {
+                self.nop_operand(self.variant.opcodes()[instruction as usize].mode)
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// run the interrupt sequence for a pending nmi, or for irq if it is not masked
+    /// returns the vector of the interrupt that was entered
+    fn service_interrupts(&mut self, irq_masked: bool) -> Option<u16> {
+        let vector = if self.nmi_pending {
+            self.nmi_pending = false;
+            NMI_VECTOR
+        } else if self.irq_line && !irq_masked {
+            IRQ_VECTOR
+        } else {
+            return None;
+        };
+
+        // the opcode at pc is fetched and dropped, then fetched again
+        self.dummy_read(self.pc);
+        self.dummy_read(self.pc);
+        self.interrupt(vector, false);
+        Some(vector)
+    }
+
+    /// read a byte from the bus, or from the 6510 i/o port
+    fn read(&mut self, address: u16) -> u8 {
+        match self.route() {
+            Route::Replay(data) => data,
+            Route::Drop => 0,
+            Route::Bus => {
+                let data = if self.variant.has_io_port() && address <= PORT_ADDRESS {
+                    self.port.read(address)
+                } else {
+                    self.mem.read(address)
+                };
+                if self.tick.running {
+                    self.tick.accesses.push(Access::Read { address, data });
+                }
+                data
+            }
+        }
     }
 
-    /// execute instructions
-    pub fn execute(&mut self) {
-        loop {
-            let instruction = self.fetch_byte();
-            match instruction {
-                LDA_IM => self.lda_im(),
-                LDA_ABS => self.lda_abs(),
-                LDA_ABSX => self.lda_absx(),
-                LDA_ABSY => self.lda_absy(),
-                LDA_ZP => self.lda_zp(),
-                LDA_ZPX => self.lda_zpx(),
-                LDA_ZPXI => self.lda_zpxi(),
-                LDA_ZPYI => self.lda_zpyi(),
-                LDX_IM => self.ldx_im(),
-                LDX_ABS => self.ldx_abs(),
-                LDX_ABSY => self.ldx_absy(),
-                LDX_ZP => self.ldx_zp(),
-                LDX_ZPY => self.ldx_zpy(),
-                LDY_IM => self.ldy_im(),
-                LDY_ABS => self.ldy_abs(),
-                LDY_ABSX => self.ldy_absx(),
-                LDY_ZP => self.ldy_zp(),
-                LDY_ZPX => self.ldy_zpx(),
-                LSR_ACC => self.lsr_acc(),
-                LSR_ABS => self.lsr_abs(),
-                LSR_ZP => self.lsr_zp(),
-                LSR_ABSX => self.lsr_absx(),
-                LSR_ZPX => self.lsr_zpx(),
-                PHA => self.pha(),
-                PHP => self.php(),
-                PLA => self.pla(),
-                PLP => self.plp(),
-                ORA_IM => self.ora_im(),
-                ORA_ABS => self.ora_abs(),
-                ORA_ABSX => self.ora_absx(),
-                ORA_ABSY => self.ora_absy(),
-                ORA_ZP => self.ora_zp(),
-                ORA_ZPX => self.ora_zpx(),
-                ORA_ZPXI => self.ora_zpxi(),
-                ORA_ZPYI => self.ora_zpyi(),
-                ANDA_IM => self.anda_im(),
-                ANDA_ABS => self.anda_abs(),
-                ANDA_ABSX => self.anda_absx(),
-                ANDA_ABSY => self.anda_absy(),
-                ANDA_ZP => self.anda_zp(),
-                ANDA_ZPX => self.anda_zpx(),
-                ANDA_ZPXI => self.anda_zpxi(),
-                ANDA_ZPYI => self.anda_zpyi(),
-                EORA_IM => self.eor_im(),
-                EORA_ABS => self.eor_abs(),
-                EORA_ABSX => self.eor_absx(),
-                EORA_ABSY => self.eor_absy(),
-                EORA_ZP => self.eor_zp(),
-                EORA_ZPX => self.eor_zpx(),
-                EORA_ZPXI => self.eor_zpxi(),
-                EORA_ZPYI => self.eor_zpyi(),
-                TAX => self.tax(),
-                TAY => self.tay(),
-                TSX => self.tsx(),
-                TXA => self.txa(),
-                TXS => self.txs(),
-                TYA => self.tya(),
-                JSR => self.jsr(),
-                RTS => self.rts(),
-                NOP => break,
-                _ => {
-                    self.debug_print();
-                    panic!("reason: unrecognized instruction");
+    /// write a byte to the bus, or to the 6510 i/o port
+    fn write(&mut self, address: u16, data: u8) {
+        let route = self.route();
+        if self.variant.has_io_port() && address <= PORT_ADDRESS {
+            // the port is inside the cpu, so a replayed write sets it again
+            if route != Route::Drop {
+                let pins = self.port.pins();
+                self.port.write(address, data);
+                if route == Route::Bus && self.port.pins() != pins {
+                    self.port_changed();
                 }
             }
+        } else if route == Route::Bus {
+            self.mem.write(address, data);
+        }
+
+        if route == Route::Bus && self.tick.running {
+            self.tick.accesses.push(Access::Write { address, data });
+        }
+    }
+
+    /// decide where the next access goes, always the bus outside `tick`
+    fn route(&mut self) -> Route {
+        let route = if !self.tick.running {
+            Route::Bus
+        } else if let Some(access) = self.tick.accesses.get(self.tick.count) {
+            match *access {
+                Access::Read { data, .. } | Access::Write { data, .. } => Route::Replay(data),
+            }
+        } else if self.tick.count < self.tick.limit {
+            Route::Bus
+        } else {
+            Route::Drop
+        };
+        self.tick.count += self.tick.running as usize;
+        self.tick.live = route == Route::Bus;
+        route
+    }
+
+    /// tell the host the i/o port pins changed
+    fn port_changed(&mut self) {
+        if let Some(hook) = self.on_port_change {
+            hook(&mut self.mem, self.port.pins());
+        }
+    }
+
+    /// read a byte and discard it, on cycles where the cpu has nothing useful to read
+    /// only done in cycle accurate mode
+    fn dummy_read(&mut self, address: u16) {
+        if self.cycle_accurate || self.tick.running {
+            self.read(address);
         }
     }
 
-    /// fetch a single byte from memory
-    fn fetch_memory(&mut self, address: usize) -> u8 {
-        self.mem.data[address]
+    /// read a little endian word from the bus, wrapping at 0xFFFF
+    fn read_word(&mut self, address: u16) -> u16 {
+        let low = self.read(address) as u16;
+        let high = self.read(address.wrapping_add(1)) as u16;
+        (high << 8) | low
     }
 
     /// fetch word from memory
     fn fetch_word(&mut self) -> u16 {
-        let mut data = self.fetch_memory(self.pc as usize) as u16;
-        self.pc += 1;
-        data |= (self.fetch_memory(self.pc as usize) as u16) << 8;
-        self.pc += 1;
+        let mut data = self.fetch_byte() as u16;
+        data |= (self.fetch_byte() as u16) << 8;
         data
     }
 
     /// fetch byte from memory
     fn fetch_byte(&mut self) -> u8 {
-        if self.pc as usize > mem::MAX_MEM {
-            panic!("PC exceeds max memory allocated {}", mem::MAX_MEM);
+        let data = self.read(self.pc);
+        self.pc = self.pc.wrapping_add(1);
+        data
+    }
+
+    /* ADDRESSING MODES */
+
+    /// zero page, x indexed address, wrapping within page zero
+    /// the unindexed address is read while x is added
+    fn addr_zpx(&mut self) -> u16 {
+        let base = self.fetch_byte();
+        self.dummy_read(base as u16);
+        base.wrapping_add(self.x) as u16
+    }
+
+    /// zero page, y indexed address, wrapping within page zero
+    /// the unindexed address is read while y is added
+    fn addr_zpy(&mut self) -> u16 {
+        let base = self.fetch_byte();
+        self.dummy_read(base as u16);
+        base.wrapping_add(self.y) as u16
+    }
+
+    /// absolute, x indexed address, wrapping at 0xFFFF
+    fn addr_absx(&mut self) -> u16 {
+        let base = self.fetch_word();
+        self.index(base, self.x)
+    }
+
+    /// absolute, y indexed address, wrapping at 0xFFFF
+    fn addr_absy(&mut self) -> u16 {
+        let base = self.fetch_word();
+        self.index(base, self.y)
+    }
+
+    /// x indexed zero page indirect address, (zp,x)
+    /// x is added to the zero page operand and the pointer stored there is used
+    fn addr_zpxi(&mut self) -> u16 {
+        let base = self.fetch_byte();
+        self.dummy_read(base as u16);
+        self.read_zp_word(base.wrapping_add(self.x))
+    }
+
+    /// zero page indirect y indexed address, (zp),y
+    /// the pointer stored at the zero page operand is used, with y added to it
+    fn addr_zpyi(&mut self) -> u16 {
+        let pointer = self.fetch_byte();
+        let base = self.read_zp_word(pointer);
+        self.index(base, self.y)
+    }
+
+    /// zero page indirect address, (zp), 65c02 only
+    fn addr_zpi(&mut self) -> u16 {
+        let pointer = self.fetch_byte();
+        self.read_zp_word(pointer)
+    }
+
+    /// add an index register to a base address, noting if a page was crossed
+    /// the index is added to the low byte first, so on a page cross the address
+    /// with the unfixed high byte is read before the carry is applied
+    fn index(&mut self, base: u16, index: u8) -> u16 {
+        let address = base.wrapping_add(index as u16);
+        self.page_crossed = (base & 0xFF00) != (address & 0xFF00);
+        if self.page_crossed {
+            self.dummy_read((base & 0xFF00) | (address & 0x00FF));
         }
+        address
+    }
 
-        let data = self.mem.data[self.pc as usize];
-        self.pc += 1;
-        data
+    /// stores and read-modify-write instructions always spend the cycle that fixes
+    /// the high byte of an indexed address, reading it even when no page was crossed
+    fn fix_index(&mut self, address: u16) {
+        if !self.page_crossed {
+            self.dummy_read(address);
+        }
+    }
+
+    /// the 65c02 only spends the fixup cycle of a shift or rotate when a page is crossed
+    fn fix_shift_index(&mut self, address: u16) {
+        if !self.variant.is_cmos() {
+            self.fix_index(address);
+        }
+    }
+
+    /// read a pointer from zero page
+    /// a pointer at 0xFF takes its high byte from 0x00, not 0x0100
+    fn read_zp_word(&mut self, address: u8) -> u16 {
+        let low = self.read(address as u16) as u16;
+        let high = self.read(address.wrapping_add(1) as u16) as u16;
+        (high << 8) | low
     }
 
     /* LOAD A INSTRUCTIONS */
@@ -181,53 +1207,56 @@ impl Cpu {
     /// load accumulator absolute
     fn lda_abs(&mut self) {
         let address = self.fetch_word();
-        self.a = self.fetch_memory(address as usize);
+        self.a = self.read(address);
+        self.set_flags();
     }
 
     /// load accumulator absolute, x index
     fn lda_absx(&mut self) {
-        let address = self.fetch_word();
-        self.a = self.fetch_memory((address + self.x as u16) as usize);
+        let address = self.addr_absx();
+        self.a = self.read(address);
         self.set_flags();
     }
 
     /// load accumulator absolute, y index
     fn lda_absy(&mut self) {
-        let address = self.fetch_word();
-        self.a = self.fetch_memory((address + self.y as u16) as usize);
+        let address = self.addr_absy();
+        self.a = self.read(address);
         self.set_flags();
     }
 
     /// load accumulator zero page
     fn lda_zp(&mut self) {
         let address = self.fetch_byte();
-        self.a = self.mem.read_byte(address as usize);
+        self.a = self.read(address as u16);
         self.set_flags();
     }
 
     /// load accumulator zero page, x index
     fn lda_zpx(&mut self) {
-        let address = self.fetch_byte();
-        self.a = self.mem.read_byte((address + self.x) as usize);
+        let address = self.addr_zpx();
+        self.a = self.read(address);
         self.set_flags();
     }
 
     /// load accumulator zero page, x index indirect
     fn lda_zpxi(&mut self) {
-        let indirect_address = self.fetch_byte() + self.x;
-        // & 0xFF will wrap to start of zero page if overflow
-        self.a = self.mem.read_byte((indirect_address & 0xFF) as usize);
+        let address = self.addr_zpxi();
+        self.a = self.read(address);
         self.set_flags();
     }
 
     /// load accumulator zero page indirect y indexed
     fn lda_zpyi(&mut self) {
-        let zero_page_address = self.fetch_byte() + self.y;
-
-        let effective_address = self.mem.read_word(zero_page_address as usize);
-        let effective_address_y = effective_address + self.y as u16;
+        let address = self.addr_zpyi();
+        self.a = self.read(address);
+        self.set_flags();
+    }
 
-        self.a = self.fetch_memory(effective_address_y as usize);
+    /// load accumulator zero page indirect
+    fn lda_zpi(&mut self) {
+        let address = self.addr_zpi();
+        self.a = self.read(address);
         self.set_flags();
     }
 
@@ -244,35 +1273,35 @@ impl Cpu {
     /// load x immediate
     fn ldx_im(&mut self) {
         self.x = self.fetch_byte();
-        self.set_flags();
+        self.set_result_flags(self.x);
     }
 
     /// load x absolute
     fn ldx_abs(&mut self) {
         let address = self.fetch_word();
-        self.x = self.fetch_memory(address as usize);
-        self.set_flags();
+        self.x = self.read(address);
+        self.set_result_flags(self.x);
     }
 
     /// load x zero page
     fn ldx_zp(&mut self) {
         let address = self.fetch_byte();
-        self.x = self.mem.read_byte(address as usize);
-        self.set_flags();
+        self.x = self.read(address as u16);
+        self.set_result_flags(self.x);
     }
 
     /// load x index y indexed absolute
     fn ldx_absy(&mut self) {
-        let address = self.fetch_word();
-        self.x = self.fetch_memory((address + self.y as u16) as usize);
-        self.set_flags();
+        let address = self.addr_absy();
+        self.x = self.read(address);
+        self.set_result_flags(self.x);
     }
 
     /// load x index y indexed zero page
     fn ldx_zpy(&mut self) {
-        let address = self.fetch_byte();
-        self.x = self.mem.read_byte((address + self.y) as usize);
-        self.set_flags();
+        let address = self.addr_zpy();
+        self.x = self.read(address);
+        self.set_result_flags(self.x);
     }
 
     /* LOAD Y INSTRUCTIONS */
@@ -280,246 +1309,607 @@ impl Cpu {
     /// load y immediate
     fn ldy_im(&mut self) {
         self.y = self.fetch_byte();
-        self.set_flags();
+        self.set_result_flags(self.y);
     }
 
     /// load y absolute
     fn ldy_abs(&mut self) {
         let address = self.fetch_word();
-        self.y = self.fetch_memory(address as usize);
-        self.set_flags();
+        self.y = self.read(address);
+        self.set_result_flags(self.y);
     }
 
     /// load y zero page
     fn ldy_zp(&mut self) {
         let address = self.fetch_byte();
-        self.y = self.mem.read_byte(address as usize);
-        self.set_flags();
+        self.y = self.read(address as u16);
+        self.set_result_flags(self.y);
     }
 
     /// load y index x indexed absolute
     fn ldy_absx(&mut self) {
-        let address = self.fetch_word();
-        self.y = self.fetch_memory((address + self.x as u16) as usize);
-        self.set_flags();
+        let address = self.addr_absx();
+        self.y = self.read(address);
+        self.set_result_flags(self.y);
     }
 
     /// load y index x indexed zero page
     fn ldy_zpx(&mut self) {
-        let address = self.fetch_byte();
-        self.y = self.mem.read_byte((address + self.x) as usize);
-        self.set_flags();
+        let address = self.addr_zpx();
+        self.y = self.read(address);
+        self.set_result_flags(self.y);
     }
 
-    /* logical shift right instructions */
-
-    /// logical shift right accumulator
-    fn lsr_acc(&mut self) {
-        self.a >>= 1;
-        self.set_flags();
+    /* STORE A INSTRUCTIONS */
 
-        self.set_carry_flag((self.a & 0b1) > 0);
+    /// store accumulator absolute
+    fn sta_abs(&mut self) {
+        let address = self.fetch_word();
+        self.write(address, self.a);
     }
 
-    /// logical shift right absolute
-    fn lsr_abs(&mut self) {
-        let address = self.fetch_word() as usize;
-        let mut data = self.mem.read_byte(address);
-
-        let carry = data & 1;
-        data >>= 1;
+    /// store accumulator absolute, x index
+    fn sta_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.write(address, self.a);
+    }
 
-        self.mem.write_byte(address, data);
+    /// store accumulator absolute, y index
+    fn sta_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.write(address, self.a);
+    }
 
-        self.p.set(ProcStat::Z, data == 0);
-        self.p.set(ProcStat::N, false);
-        self.set_carry_flag(carry > 0);
+    /// store accumulator zero page
+    fn sta_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.write(address as u16, self.a);
     }
 
-    /// logical shift right zero page
-    fn lsr_zp(&mut self) {
-        let address = self.fetch_byte() as usize;
-        let mut data = self.mem.read_byte(address);
+    /// store accumulator zero page, x index
+    fn sta_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.write(address, self.a);
+    }
 
-        let carry = data & 1;
-        data >>= 1;
+    /// store accumulator zero page, x index indirect
+    fn sta_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.write(address, self.a);
+    }
 
-        self.mem.write_byte(address, data);
+    /// store accumulator zero page indirect y indexed
+    fn sta_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.write(address, self.a);
+    }
 
-        self.p.set(ProcStat::Z, data == 0);
-        self.p.set(ProcStat::N, false);
-        self.set_carry_flag(carry > 0);
+    /// store accumulator zero page indirect
+    fn sta_zpi(&mut self) {
+        let address = self.addr_zpi();
+        self.write(address, self.a);
     }
 
-    /// logical shift right absolute, x index
-    fn lsr_absx(&mut self) {
-        let address = self.fetch_word() as usize;
-        let mut data = self.mem.read_byte(address + self.x as usize);
+    /* STORE X INSTRUCTIONS */
 
-        let carry = data & 1;
-        data >>= 1;
-        self.mem.write_byte(address + self.x as usize, data >> 1);
+    /// store x absolute
+    fn stx_abs(&mut self) {
+        let address = self.fetch_word();
+        self.write(address, self.x);
+    }
 
-        self.p.set(ProcStat::Z, data == 0);
-        self.p.set(ProcStat::N, false);
-        self.set_carry_flag(carry > 0);
+    /// store x zero page
+    fn stx_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.write(address as u16, self.x);
     }
 
-    /// logical shift right zero page, x index
-    fn lsr_zpx(&mut self) {
-        let address = self.fetch_byte() as usize;
-        let data = self.mem.read_byte(address + self.x as usize);
+    /// store x index y indexed zero page
+    fn stx_zpy(&mut self) {
+        let address = self.addr_zpy();
+        self.write(address, self.x);
+    }
 
-        self.mem.write_byte(address + self.x as usize, data >> 1);
+    /* STORE Y INSTRUCTIONS */
 
-        self.set_flags();
-        self.set_carry_flag((data & 1) > 0);
+    /// store y absolute
+    fn sty_abs(&mut self) {
+        let address = self.fetch_word();
+        self.write(address, self.y);
     }
 
-    /* PUSH INSTRUCTIONS */
+    /// store y zero page
+    fn sty_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.write(address as u16, self.y);
+    }
 
-    /// push accumulator onto stack
-    fn pha(&mut self) {
-        self.mem.write_byte(self.sp as usize, self.a);
-        self.sp -= 1;
+    /// store y index x indexed zero page
+    fn sty_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.write(address, self.y);
     }
 
-    /// push processor status onto stack
-    fn php(&mut self) {
-        self.mem.write_byte(self.sp as usize, self.p.bits());
-        self.sp -= 1;
+    /* STORE ZERO INSTRUCTIONS */
+
+    /// store zero zero page
+    fn stz_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.write(address as u16, 0);
     }
 
-    /* POP INSTRUCTIONS */
+    /// store zero zero page, x index
+    fn stz_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.write(address, 0);
+    }
 
-    /// pop accumulator from stack
-    fn pla(&mut self) {
-        self.sp += 1;
-        self.a = self.mem.read_byte(self.sp as usize);
-        self.set_flags();
+    /// store zero absolute
+    fn stz_abs(&mut self) {
+        let address = self.fetch_word();
+        self.write(address, 0);
     }
 
-    /// pop processor status from stack
-    fn plp(&mut self) {
-        self.sp += 1;
-        self.p = ProcStat::from_bits_truncate(self.mem.read_byte(self.sp as usize));
+    /// store zero absolute, x index
+    fn stz_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.write(address, 0);
     }
 
-    /* ORA INSTRUCTIONS */
+    /* SHIFT AND ROTATE INSTRUCTIONS */
 
-    /// or accumulator with immediate
-    fn ora_im(&mut self) {
-        self.a |= self.fetch_byte();
-        self.set_flags();
+    /// arithmetic shift left accumulator
+    fn asl_acc(&mut self) {
+        self.a = self.asl(self.a);
     }
 
-    /// or accumulator with absolute
-    fn ora_abs(&mut self) {
+    /// arithmetic shift left absolute
+    fn asl_abs(&mut self) {
         let address = self.fetch_word();
-        self.a |= self.fetch_memory(address as usize);
-        self.set_flags();
+        self.modify(address, Self::asl);
     }
 
-    /// or accumulator with zero page
-    fn ora_zp(&mut self) {
+    /// arithmetic shift left zero page
+    fn asl_zp(&mut self) {
         let address = self.fetch_byte();
-        self.a |= self.mem.read_byte(address as usize);
-        self.set_flags();
+        self.modify(address as u16, Self::asl);
     }
 
-    /// or accumulator with absolute, x index
-    fn ora_absx(&mut self) {
-        let address = self.fetch_word();
-        self.a |= self.fetch_memory((address + self.x as u16) as usize);
-        self.set_flags();
+    /// arithmetic shift left absolute, x index
+    fn asl_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_shift_index(address);
+        self.modify(address, Self::asl);
     }
 
-    /// or accumulator with absolute, y index
-    fn ora_absy(&mut self) {
-        let address = self.fetch_word();
-        self.a |= self.fetch_memory((address + self.y as u16) as usize);
-        self.set_flags();
+    /// arithmetic shift left zero page, x index
+    fn asl_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::asl);
     }
 
-    /// or accumulator with zero page, x index
-    fn ora_zpx(&mut self) {
-        let address = self.fetch_byte();
-        self.a |= self.mem.read_byte((address + self.x) as usize);
-        self.set_flags();
+    /// logical shift right accumulator
+    fn lsr_acc(&mut self) {
+        self.a = self.lsr(self.a);
     }
 
-    /// or accumulator with indirect, x index
-    fn ora_zpxi(&mut self) {
-        let address = self.fetch_byte();
-        let eff_address = self.mem.read_word((address + self.x) as usize);
-        self.a |= self.fetch_memory(eff_address as usize);
-        self.set_flags();
+    /// logical shift right absolute
+    fn lsr_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::lsr);
     }
 
-    /// or accumulator with indirect, y index
-    fn ora_zpyi(&mut self) {
+    /// logical shift right zero page
+    fn lsr_zp(&mut self) {
         let address = self.fetch_byte();
-        let eff_address = self.mem.read_word(address as usize) + self.y as u16;
-        self.a |= self.fetch_memory(eff_address as usize);
-        self.set_flags();
+        self.modify(address as u16, Self::lsr);
     }
 
-    /* ANDA instructions */
+    /// logical shift right absolute, x index
+    fn lsr_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_shift_index(address);
+        self.modify(address, Self::lsr);
+    }
 
-    /// and accumulator with immediate
-    fn anda_im(&mut self) {
-        self.a &= self.fetch_byte();
-        self.set_flags();
+    /// logical shift right zero page, x index
+    fn lsr_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::lsr);
     }
 
-    /// and accumulator with absolute
+    /// rotate left accumulator
+    fn rol_acc(&mut self) {
+        self.a = self.rol(self.a);
+    }
+
+    /// rotate left absolute
+    fn rol_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::rol);
+    }
+
+    /// rotate left zero page
+    fn rol_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::rol);
+    }
+
+    /// rotate left absolute, x index
+    fn rol_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_shift_index(address);
+        self.modify(address, Self::rol);
+    }
+
+    /// rotate left zero page, x index
+    fn rol_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::rol);
+    }
+
+    /// rotate right accumulator
+    fn ror_acc(&mut self) {
+        self.a = self.ror(self.a);
+    }
+
+    /// rotate right absolute
+    fn ror_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::ror);
+    }
+
+    /// rotate right zero page
+    fn ror_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::ror);
+    }
+
+    /// rotate right absolute, x index
+    fn ror_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_shift_index(address);
+        self.modify(address, Self::ror);
+    }
+
+    /// rotate right zero page, x index
+    fn ror_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::ror);
+    }
+
+    /// shift value left, moving bit 7 into carry
+    fn asl(&mut self, value: u8) -> u8 {
+        let result = value << 1;
+        self.set_carry_flag((value & 0x80) > 0);
+        self.set_result_flags(result);
+        result
+    }
+
+    /// shift value right, moving bit 0 into carry
+    fn lsr(&mut self, value: u8) -> u8 {
+        let result = value >> 1;
+        self.set_carry_flag((value & 0x01) > 0);
+        self.set_result_flags(result);
+        result
+    }
+
+    /// rotate value left through carry
+    fn rol(&mut self, value: u8) -> u8 {
+        let result = (value << 1) | self.p.contains(ProcStat::C) as u8;
+        self.set_carry_flag((value & 0x80) > 0);
+        self.set_result_flags(result);
+        result
+    }
+
+    /// rotate value right through carry
+    fn ror(&mut self, value: u8) -> u8 {
+        let result = (value >> 1) | ((self.p.contains(ProcStat::C) as u8) << 7);
+        self.set_carry_flag((value & 0x01) > 0);
+        self.set_result_flags(result);
+        result
+    }
+
+    /* INCREMENT AND DECREMENT INSTRUCTIONS */
+
+    /// increment memory absolute
+    fn inc_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::inc);
+    }
+
+    /// increment memory zero page
+    fn inc_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::inc);
+    }
+
+    /// increment memory absolute, x index
+    fn inc_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::inc);
+    }
+
+    /// increment memory zero page, x index
+    fn inc_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::inc);
+    }
+
+    /// decrement memory absolute
+    fn dec_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::dec);
+    }
+
+    /// decrement memory zero page
+    fn dec_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::dec);
+    }
+
+    /// decrement memory absolute, x index
+    fn dec_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::dec);
+    }
+
+    /// decrement memory zero page, x index
+    fn dec_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::dec);
+    }
+
+    /// increment accumulator
+    fn inc_acc(&mut self) {
+        self.a = self.inc(self.a);
+    }
+
+    /// decrement accumulator
+    fn dec_acc(&mut self) {
+        self.a = self.dec(self.a);
+    }
+
+    /// add one to value
+    fn inc(&mut self, value: u8) -> u8 {
+        let result = value.wrapping_add(1);
+        self.set_result_flags(result);
+        result
+    }
+
+    /// subtract one from value
+    fn dec(&mut self, value: u8) -> u8 {
+        let result = value.wrapping_sub(1);
+        self.set_result_flags(result);
+        result
+    }
+
+    /// increment x register
+    fn inx(&mut self) {
+        self.x = self.inc(self.x);
+    }
+
+    /// increment y register
+    fn iny(&mut self) {
+        self.y = self.inc(self.y);
+    }
+
+    /// decrement x register
+    fn dex(&mut self) {
+        self.x = self.dec(self.x);
+    }
+
+    /// decrement y register
+    fn dey(&mut self) {
+        self.y = self.dec(self.y);
+    }
+
+    /// read-modify-write a memory location
+    /// like the nmos 6502, the unmodified value is written back
+    /// before the result, so the bus sees both writes.
+    /// the 65c02 reads the location a second time instead
+    fn modify<F>(&mut self, address: u16, operation: F)
+    where
+        F: FnOnce(&mut Self, u8) -> u8,
+    {
+        let value = self.read(address);
+        if self.variant.is_cmos() {
+            self.dummy_read(address);
+        } else {
+            self.write(address, value);
+        }
+        let result = operation(self, value);
+        self.write(address, result);
+    }
+
+    /// set zero and negative flags from the result of an operation
+    fn set_result_flags(&mut self, result: u8) {
+        self.p.set(ProcStat::Z, result == 0);
+        self.p.set(ProcStat::N, (result & 0x80) > 0);
+    }
+
+    /* PUSH INSTRUCTIONS */
+
+    /// push accumulator onto stack
+    fn pha(&mut self) {
+        self.push_byte(self.a);
+    }
+
+    /// push processor status onto stack
+    fn php(&mut self) {
+        self.push_byte(self.p.to_stack(true));
+    }
+
+    /// push x register onto stack
+    fn phx(&mut self) {
+        self.push_byte(self.x);
+    }
+
+    /// push y register onto stack
+    fn phy(&mut self) {
+        self.push_byte(self.y);
+    }
+
+    /* POP INSTRUCTIONS */
+
+    /// pop accumulator from stack
+    fn pla(&mut self) {
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        self.a = self.pull_byte();
+        self.set_flags();
+    }
+
+    /// pop processor status from stack
+    fn plp(&mut self) {
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        self.p = ProcStat::from_stack(self.pull_byte());
+    }
+
+    /// pop x register from stack
+    fn plx(&mut self) {
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        self.x = self.pull_byte();
+        self.set_result_flags(self.x);
+    }
+
+    /// pop y register from stack
+    fn ply(&mut self) {
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        self.y = self.pull_byte();
+        self.set_result_flags(self.y);
+    }
+
+    /* ORA INSTRUCTIONS */
+
+    /// or accumulator with immediate
+    fn ora_im(&mut self) {
+        self.a |= self.fetch_byte();
+        self.set_flags();
+    }
+
+    /// or accumulator with absolute
+    fn ora_abs(&mut self) {
+        let address = self.fetch_word();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /// or accumulator with zero page
+    fn ora_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.a |= self.read(address as u16);
+        self.set_flags();
+    }
+
+    /// or accumulator with absolute, x index
+    fn ora_absx(&mut self) {
+        let address = self.addr_absx();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /// or accumulator with absolute, y index
+    fn ora_absy(&mut self) {
+        let address = self.addr_absy();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /// or accumulator with zero page, x index
+    fn ora_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /// or accumulator with indirect, x index
+    fn ora_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /// or accumulator with indirect, y index
+    fn ora_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /// or accumulator with zero page indirect
+    fn ora_zpi(&mut self) {
+        let address = self.addr_zpi();
+        self.a |= self.read(address);
+        self.set_flags();
+    }
+
+    /* ANDA instructions */
+
+    /// and accumulator with immediate
+    fn anda_im(&mut self) {
+        self.a &= self.fetch_byte();
+        self.set_flags();
+    }
+
+    /// and accumulator with absolute
     fn anda_abs(&mut self) {
         let address = self.fetch_word();
-        self.a &= self.fetch_memory(address as usize);
+        self.a &= self.read(address);
         self.set_flags();
     }
 
     /// and accumulator with zero page
     fn anda_zp(&mut self) {
         let address = self.fetch_byte();
-        self.a &= self.mem.read_byte(address as usize);
+        self.a &= self.read(address as u16);
         self.set_flags();
     }
 
     /// and accumulator with absolute, x index
     fn anda_absx(&mut self) {
-        let address = self.fetch_word();
-        self.a &= self.fetch_memory((address + self.x as u16) as usize);
+        let address = self.addr_absx();
+        self.a &= self.read(address);
         self.set_flags();
     }
 
     /// and accumulator with absolute, y index
     fn anda_absy(&mut self) {
-        let address = self.fetch_word();
-        self.a &= self.fetch_memory((address + self.y as u16) as usize);
+        let address = self.addr_absy();
+        self.a &= self.read(address);
         self.set_flags();
     }
 
     /// and accumulator with zero page, x index
     fn anda_zpx(&mut self) {
-        let address = self.fetch_byte();
-        self.a &= self.mem.read_byte((address + self.x) as usize);
+        let address = self.addr_zpx();
+        self.a &= self.read(address);
         self.set_flags();
     }
 
     /// and accumulator with indirect, x index
     fn anda_zpxi(&mut self) {
-        let address = self.fetch_byte();
-        let eff_address = self.mem.read_word((address + self.x) as usize);
-        self.a &= self.fetch_memory(eff_address as usize);
+        let address = self.addr_zpxi();
+        self.a &= self.read(address);
         self.set_flags();
     }
 
     /// and accumulator with indirect, y index
     fn anda_zpyi(&mut self) {
-        let address = self.fetch_byte();
-        let eff_address = self.mem.read_word(address as usize) + self.y as u16;
-        self.a &= self.fetch_memory(eff_address as usize);
+        let address = self.addr_zpyi();
+        self.a &= self.read(address);
+        self.set_flags();
+    }
+
+    /// and accumulator with zero page indirect
+    fn anda_zpi(&mut self) {
+        let address = self.addr_zpi();
+        self.a &= self.read(address);
         self.set_flags();
     }
 
@@ -534,119 +1924,1331 @@ impl Cpu {
     /// exclusive or accumulator with absolute
     fn eor_abs(&mut self) {
         let address = self.fetch_word();
-        self.a ^= self.fetch_memory(address as usize);
+        self.a ^= self.read(address);
         self.set_flags();
     }
 
     /// exclusive or accumulator with zero page
     fn eor_zp(&mut self) {
         let address = self.fetch_byte();
-        self.a ^= self.mem.read_byte(address as usize);
+        self.a ^= self.read(address as u16);
         self.set_flags();
     }
 
     /// exclusive or accumulator with absolute, x index
     fn eor_absx(&mut self) {
-        let address = self.fetch_word();
-        self.a ^= self.fetch_memory((address + self.x as u16) as usize);
+        let address = self.addr_absx();
+        self.a ^= self.read(address);
         self.set_flags();
     }
 
     /// exclusive or accumulator with absolute, y index
     fn eor_absy(&mut self) {
-        let address = self.fetch_word();
-        self.a ^= self.fetch_memory((address + self.y as u16) as usize);
+        let address = self.addr_absy();
+        self.a ^= self.read(address);
         self.set_flags();
     }
 
     /// exclusive or accumulator with zero page, x index
     fn eor_zpx(&mut self) {
-        let address = self.fetch_byte();
-        self.a ^= self.mem.read_byte((address + self.x) as usize);
+        let address = self.addr_zpx();
+        self.a ^= self.read(address);
         self.set_flags();
     }
 
     /// exclusive or accumulator with indirect, x index
     fn eor_zpxi(&mut self) {
-        let address = self.fetch_byte();
-        let eff_address = self.mem.read_word((address + self.x) as usize);
-        self.a ^= self.fetch_memory(eff_address as usize);
+        let address = self.addr_zpxi();
+        self.a ^= self.read(address);
         self.set_flags();
     }
 
     /// exclusive or accumulator with indirect, y index
     fn eor_zpyi(&mut self) {
-        let address = self.fetch_byte();
-        let eff_address = self.mem.read_word(address as usize) + self.y as u16;
-        self.a ^= self.fetch_memory(eff_address as usize);
+        let address = self.addr_zpyi();
+        self.a ^= self.read(address);
         self.set_flags();
     }
 
-    /* TRANSFER INSTRUCTIONS */
+    /// exclusive or accumulator with zero page indirect
+    fn eor_zpi(&mut self) {
+        let address = self.addr_zpi();
+        self.a ^= self.read(address);
+        self.set_flags();
+    }
 
-    /// transfer accumulator to x register
-    fn tax(&mut self) {
-        self.x = self.a;
-        self.p.set(ProcStat::Z, self.x == 0);
-        self.p.set(ProcStat::N, (self.x & 0x80) > 0);
+    /* ADC INSTRUCTIONS */
+
+    /// add with carry immediate
+    fn adc_im(&mut self) {
+        let value = self.fetch_byte();
+        self.adc(value);
     }
 
-    /// transfer accumulator to y register
-    fn tay(&mut self) {
-        self.y = self.a;
-        self.p.set(ProcStat::Z, self.y == 0);
-        self.p.set(ProcStat::N, (self.y & 0x80) > 0);
+    /// add with carry absolute
+    fn adc_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.adc(value);
     }
 
-    /// transfer x register to accumulator
-    fn txa(&mut self) {
-        self.a = self.x;
-        self.p.set(ProcStat::Z, self.a == 0);
-        self.p.set(ProcStat::N, (self.a & 0x80) > 0);
+    /// add with carry zero page
+    fn adc_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.adc(value);
     }
 
-    /// transfer y register to accumulator
-    fn tya(&mut self) {
-        self.a = self.y;
-        self.p.set(ProcStat::Z, self.a == 0);
-        self.p.set(ProcStat::N, (self.a & 0x80) > 0);
+    /// add with carry absolute, x index
+    fn adc_absx(&mut self) {
+        let address = self.addr_absx();
+        let value = self.read(address);
+        self.adc(value);
     }
 
-    /// transfer x register to stack pointer
-    fn txs(&mut self) {
-        self.sp = 0x0100 | self.x as u16;
+    /// add with carry absolute, y index
+    fn adc_absy(&mut self) {
+        let address = self.addr_absy();
+        let value = self.read(address);
+        self.adc(value);
     }
 
-    /// transfer stack pointer to x register
-    fn tsx(&mut self) {
-        self.x = (self.sp & 0x00FF) as u8;
+    /// add with carry zero page, x index
+    fn adc_zpx(&mut self) {
+        let address = self.addr_zpx();
+        let value = self.read(address);
+        self.adc(value);
+    }
 
-        self.p.set(ProcStat::Z, self.x == 0);
-        self.p.set(ProcStat::N, (self.x & 0x80) > 0);
+    /// add with carry indirect, x index
+    fn adc_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        let value = self.read(address);
+        self.adc(value);
     }
 
-    /// sets the carry bit if flag is true in processor status register
-    fn set_carry_flag(&mut self, carry: bool) {
-        self.p.set(ProcStat::C, carry);
+    /// add with carry indirect, y index
+    fn adc_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        let value = self.read(address);
+        self.adc(value);
     }
 
-    /// jump to a subroutine by pushing the pc onto the stack and modifying the pc
-    fn jsr(&mut self) {
-        let sub_address = self.fetch_word();
-        self.mem.write_word(self.sp as usize, self.pc - 1);
-        self.sp -= 2;
-        self.pc = sub_address;
+    /// add with carry zero page indirect
+    fn adc_zpi(&mut self) {
+        let address = self.addr_zpi();
+        let value = self.read(address);
+        self.adc(value);
     }
 
-    /// return from subroutine, taking PC from stack and continuing before the jump
-    fn rts(&mut self) {
-        self.sp += 1;
-        let pch = self.mem.read_byte(self.sp as usize);
-        self.sp += 1;
-        let pcl = self.mem.read_byte(self.sp as usize);
-        self.pc = (((pch as u16) << 8) | pcl as u16) + 1;
+    /* SBC INSTRUCTIONS */
+
+    /// subtract with borrow immediate
+    fn sbc_im(&mut self) {
+        let value = self.fetch_byte();
+        self.sbc(value);
     }
 
-    /// no-op (do nothing)
-    fn nop(&mut self) {}
-}
\ No newline at end of file
+    /// subtract with borrow absolute
+    fn sbc_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow zero page
+    fn sbc_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow absolute, x index
+    fn sbc_absx(&mut self) {
+        let address = self.addr_absx();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow absolute, y index
+    fn sbc_absy(&mut self) {
+        let address = self.addr_absy();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow zero page, x index
+    fn sbc_zpx(&mut self) {
+        let address = self.addr_zpx();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow indirect, x index
+    fn sbc_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow indirect, y index
+    fn sbc_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// subtract with borrow zero page indirect
+    fn sbc_zpi(&mut self) {
+        let address = self.addr_zpi();
+        let value = self.read(address);
+        self.sbc(value);
+    }
+
+    /// add a value and the carry to the accumulator
+    /// in decimal mode the nmos 6502 takes N, V and Z from intermediate results
+    /// rather than the final bcd value, see http://www.6502.org/tutorials/decimal_mode.html
+    fn adc(&mut self, value: u8) {
+        let carry = self.p.contains(ProcStat::C) as u16;
+        let binary = self.a as u16 + value as u16 + carry;
+
+        if !self.decimal_mode() {
+            self.p.set(ProcStat::C, binary > 0xFF);
+            self.p.set(
+                ProcStat::V,
+                (!(self.a ^ value) & (self.a ^ binary as u8) & 0x80) > 0,
+            );
+            self.a = binary as u8;
+            self.set_flags();
+            return;
+        }
+
+        // add the low nibbles and adjust them into the 0-9 range
+        let mut low = (self.a & 0x0F) as u16 + (value & 0x0F) as u16 + carry;
+        if low >= 0x0A {
+            low = ((low + 0x06) & 0x0F) + 0x10;
+        }
+
+        // N and V are taken before the high nibble is adjusted
+        let mut result = (self.a & 0xF0) as u16 + (value & 0xF0) as u16 + low;
+        self.p.set(ProcStat::N, (result & 0x80) > 0);
+        self.p.set(
+            ProcStat::V,
+            (!(self.a ^ value) & (self.a ^ result as u8) & 0x80) > 0,
+        );
+
+        if result >= 0xA0 {
+            result += 0x60;
+        }
+
+        // Z comes from the binary sum
+        self.p.set(ProcStat::Z, (binary & 0xFF) == 0);
+        self.set_carry_flag(result >= 0x100);
+        self.a = result as u8;
+
+        // the 65c02 spends a cycle to set N and Z from the bcd result
+        if self.variant.is_cmos() {
+            self.set_flags();
+            self.extra_cycles += 1;
+        }
+    }
+
+    /// subtract a value and the inverted carry (borrow) from the accumulator
+    /// in decimal mode the nmos 6502 sets every flag from the binary result,
+    /// the 65c02 adjusts the result differently and takes N and Z from it
+    fn sbc(&mut self, value: u8) {
+        if !self.decimal_mode() {
+            // binary subtraction is addition of the one's complement
+            self.adc(!value);
+            return;
+        }
+
+        let borrow = !self.p.contains(ProcStat::C) as i16;
+        let binary = self.a as i16 - value as i16 - borrow;
+
+        let mut low = (self.a & 0x0F) as i16 - (value & 0x0F) as i16 - borrow;
+        let result = if self.variant.is_cmos() {
+            // the 65c02 adjusts the whole binary result
+            let mut result = binary;
+            if result < 0 {
+                result -= 0x60;
+            }
+            if low < 0 {
+                result -= 0x06;
+            }
+            result
+        } else {
+            // adjust the low nibble back into the 0-9 range, then the high one
+            if low < 0 {
+                low = ((low - 0x06) & 0x0F) - 0x10;
+            }
+            let mut result = (self.a & 0xF0) as i16 - (value & 0xF0) as i16 + low;
+            if result < 0 {
+                result -= 0x60;
+            }
+            result
+        };
+
+        self.set_carry_flag(binary >= 0);
+        self.p.set(
+            ProcStat::V,
+            ((self.a ^ value) & (self.a ^ binary as u8) & 0x80) > 0,
+        );
+        self.p.set(ProcStat::Z, (binary & 0xFF) == 0);
+        self.p.set(ProcStat::N, (binary & 0x80) > 0);
+        self.a = result as u8;
+
+        if self.variant.is_cmos() {
+            self.set_flags();
+            self.extra_cycles += 1;
+        }
+    }
+
+    /// true if ADC and SBC work in bcd, the D flag is set and the variant has decimal mode
+    fn decimal_mode(&self) -> bool {
+        self.p.contains(ProcStat::D) && self.variant.has_decimal()
+    }
+
+    /* COMPARE INSTRUCTIONS */
+
+    /// compare accumulator with immediate
+    fn cmp_im(&mut self) {
+        let value = self.fetch_byte();
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with absolute
+    fn cmp_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with zero page
+    fn cmp_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with absolute, x index
+    fn cmp_absx(&mut self) {
+        let address = self.addr_absx();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with absolute, y index
+    fn cmp_absy(&mut self) {
+        let address = self.addr_absy();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with zero page, x index
+    fn cmp_zpx(&mut self) {
+        let address = self.addr_zpx();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with indirect, x index
+    fn cmp_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with indirect, y index
+    fn cmp_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare accumulator with zero page indirect
+    fn cmp_zpi(&mut self) {
+        let address = self.addr_zpi();
+        let value = self.read(address);
+        self.compare(self.a, value);
+    }
+
+    /// compare x register with immediate
+    fn cpx_im(&mut self) {
+        let value = self.fetch_byte();
+        self.compare(self.x, value);
+    }
+
+    /// compare x register with absolute
+    fn cpx_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.compare(self.x, value);
+    }
+
+    /// compare x register with zero page
+    fn cpx_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.compare(self.x, value);
+    }
+
+    /// compare y register with immediate
+    fn cpy_im(&mut self) {
+        let value = self.fetch_byte();
+        self.compare(self.y, value);
+    }
+
+    /// compare y register with absolute
+    fn cpy_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.compare(self.y, value);
+    }
+
+    /// compare y register with zero page
+    fn cpy_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.compare(self.y, value);
+    }
+
+    /// set carry, zero and negative flags as if value was subtracted from register
+    fn compare(&mut self, register: u8, value: u8) {
+        let result = register.wrapping_sub(value);
+        self.set_carry_flag(register >= value);
+        self.p.set(ProcStat::Z, register == value);
+        self.p.set(ProcStat::N, (result & 0x80) > 0);
+    }
+
+    /* BIT INSTRUCTIONS */
+
+    /// test bits in accumulator with absolute
+    fn bit_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.bit(value);
+    }
+
+    /// test bits in accumulator with zero page
+    fn bit_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.bit(value);
+    }
+
+    /// test bits in accumulator with immediate, only Z is affected
+    fn bit_im(&mut self) {
+        let value = self.fetch_byte();
+        self.p.set(ProcStat::Z, (self.a & value) == 0);
+    }
+
+    /// test bits in accumulator with zero page, x index
+    fn bit_zpx(&mut self) {
+        let address = self.addr_zpx();
+        let value = self.read(address);
+        self.bit(value);
+    }
+
+    /// test bits in accumulator with absolute, x index
+    fn bit_absx(&mut self) {
+        let address = self.addr_absx();
+        let value = self.read(address);
+        self.bit(value);
+    }
+
+    /// set zero flag from accumulator and value, and copy bits 7 and 6 of value into N and V
+    fn bit(&mut self, value: u8) {
+        self.p.set(ProcStat::Z, (self.a & value) == 0);
+        self.p.set(ProcStat::N, (value & 0x80) > 0);
+        self.p.set(ProcStat::V, (value & 0x40) > 0);
+    }
+
+    /* TEST AND SET INSTRUCTIONS */
+
+    /// test and set bits absolute
+    fn tsb_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::tsb);
+    }
+
+    /// test and set bits zero page
+    fn tsb_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::tsb);
+    }
+
+    /// test and reset bits absolute
+    fn trb_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::trb);
+    }
+
+    /// test and reset bits zero page
+    fn trb_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::trb);
+    }
+
+    /// set zero flag from accumulator and value, then set the accumulator's bits in value
+    fn tsb(&mut self, value: u8) -> u8 {
+        self.p.set(ProcStat::Z, (self.a & value) == 0);
+        value | self.a
+    }
+
+    /// set zero flag from accumulator and value, then clear the accumulator's bits in value
+    fn trb(&mut self, value: u8) -> u8 {
+        self.p.set(ProcStat::Z, (self.a & value) == 0);
+        value & !self.a
+    }
+
+    /* BRANCH INSTRUCTIONS */
+
+    /// branch if negative flag is clear
+    fn bpl(&mut self) {
+        self.branch(!self.p.contains(ProcStat::N));
+    }
+
+    /// branch if negative flag is set
+    fn bmi(&mut self) {
+        self.branch(self.p.contains(ProcStat::N));
+    }
+
+    /// branch if overflow flag is clear
+    fn bvc(&mut self) {
+        self.branch(!self.p.contains(ProcStat::V));
+    }
+
+    /// branch if overflow flag is set
+    fn bvs(&mut self) {
+        self.branch(self.p.contains(ProcStat::V));
+    }
+
+    /// branch if carry flag is clear
+    fn bcc(&mut self) {
+        self.branch(!self.p.contains(ProcStat::C));
+    }
+
+    /// branch if carry flag is set
+    fn bcs(&mut self) {
+        self.branch(self.p.contains(ProcStat::C));
+    }
+
+    /// branch if zero flag is clear
+    fn bne(&mut self) {
+        self.branch(!self.p.contains(ProcStat::Z));
+    }
+
+    /// branch if zero flag is set
+    fn beq(&mut self) {
+        self.branch(self.p.contains(ProcStat::Z));
+    }
+
+    /// branch always
+    fn bra(&mut self) {
+        self.branch(true);
+    }
+
+    /// fetch a signed offset and add it to the pc if condition holds
+    /// the offset is relative to the address after the operand
+    /// a taken branch costs one extra cycle, and another if it lands on a different page,
+    /// both spent reading from the pc before it is fully updated
+    fn branch(&mut self, condition: bool) {
+        let offset = self.fetch_byte() as i8;
+        if !condition {
+            return;
+        }
+
+        let target = self.pc.wrapping_add(offset as u16);
+        self.dummy_read(self.pc);
+        self.extra_cycles += 1;
+        if (target & 0xFF00) != (self.pc & 0xFF00) {
+            self.dummy_read((self.pc & 0xFF00) | (target & 0x00FF));
+            self.extra_cycles += 1;
+        }
+
+        self.pc = target;
+    }
+
+    /* TRANSFER INSTRUCTIONS */
+
+    /// transfer accumulator to x register
+    fn tax(&mut self) {
+        self.x = self.a;
+        self.p.set(ProcStat::Z, self.x == 0);
+        self.p.set(ProcStat::N, (self.x & 0x80) > 0);
+    }
+
+    /// transfer accumulator to y register
+    fn tay(&mut self) {
+        self.y = self.a;
+        self.p.set(ProcStat::Z, self.y == 0);
+        self.p.set(ProcStat::N, (self.y & 0x80) > 0);
+    }
+
+    /// transfer x register to accumulator
+    fn txa(&mut self) {
+        self.a = self.x;
+        self.p.set(ProcStat::Z, self.a == 0);
+        self.p.set(ProcStat::N, (self.a & 0x80) > 0);
+    }
+
+    /// transfer y register to accumulator
+    fn tya(&mut self) {
+        self.a = self.y;
+        self.p.set(ProcStat::Z, self.a == 0);
+        self.p.set(ProcStat::N, (self.a & 0x80) > 0);
+    }
+
+    /// transfer x register to stack pointer
+    fn txs(&mut self) {
+        self.sp = self.x;
+    }
+
+    /// transfer stack pointer to x register
+    fn tsx(&mut self) {
+        self.x = self.sp;
+
+        self.p.set(ProcStat::Z, self.x == 0);
+        self.p.set(ProcStat::N, (self.x & 0x80) > 0);
+    }
+
+    /// sets the carry bit if flag is true in processor status register
+    fn set_carry_flag(&mut self, carry: bool) {
+        self.p.set(ProcStat::C, carry);
+    }
+
+    /// jump to absolute address
+    fn jmp_abs(&mut self) {
+        self.pc = self.fetch_word();
+    }
+
+    /// jump to the address stored at the absolute vector
+    /// the 65c02 spends an extra cycle so the vector can cross a page
+    fn jmp_ind(&mut self) {
+        let vector = self.fetch_word();
+        if self.variant.is_cmos() {
+            self.dummy_read(self.pc.wrapping_sub(1));
+        }
+        let low = self.read(vector) as u16;
+
+        let high_address = if self.jmp_indirect_bug {
+            // the nmos part never carries into the high byte of the vector
+            (vector & 0xFF00) | (vector.wrapping_add(1) & 0x00FF)
+        } else {
+            vector.wrapping_add(1)
+        };
+        let high = self.read(high_address) as u16;
+
+        self.pc = (high << 8) | low;
+    }
+
+    /// jump to the address stored at the absolute vector plus x
+    fn jmp_absxi(&mut self) {
+        let base = self.fetch_word();
+        self.dummy_read(self.pc.wrapping_sub(1));
+        self.pc = self.read_word(base.wrapping_add(self.x as u16));
+    }
+
+    /// jump to a subroutine by pushing the pc onto the stack and modifying the pc
+    /// the high byte of the target is fetched after the push, so the pushed
+    /// return address is the last byte of the instruction
+    fn jsr(&mut self) {
+        let low = self.fetch_byte() as u16;
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        self.push_byte((self.pc >> 8) as u8);
+        self.push_byte(self.pc as u8);
+        let high = self.fetch_byte() as u16;
+        self.pc = (high << 8) | low;
+    }
+
+    /// return from subroutine, taking PC from stack and continuing before the jump
+    fn rts(&mut self) {
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        let pcl = self.pull_byte();
+        let pch = self.pull_byte();
+        self.pc = ((pch as u16) << 8) | pcl as u16;
+        self.dummy_read(self.pc);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    /* FLAG INSTRUCTIONS */
+
+    /// clear carry flag
+    fn clc(&mut self) {
+        self.p.remove(ProcStat::C);
+    }
+
+    /// set carry flag
+    fn sec(&mut self) {
+        self.p.insert(ProcStat::C);
+    }
+
+    /// clear interrupt disable flag
+    fn cli(&mut self) {
+        self.p.remove(ProcStat::I);
+    }
+
+    /// set interrupt disable flag
+    fn sei(&mut self) {
+        self.p.insert(ProcStat::I);
+    }
+
+    /// clear decimal flag
+    fn cld(&mut self) {
+        self.p.remove(ProcStat::D);
+    }
+
+    /// set decimal flag
+    fn sed(&mut self) {
+        self.p.insert(ProcStat::D);
+    }
+
+    /// clear overflow flag
+    fn clv(&mut self) {
+        self.p.remove(ProcStat::V);
+    }
+
+    /* INTERRUPT INSTRUCTIONS */
+
+    /// software interrupt, skips the padding byte after the opcode
+    /// so that RTI returns to PC+2
+    fn brk(&mut self) {
+        self.fetch_byte();
+        self.interrupt(IRQ_VECTOR, true);
+    }
+
+    /// return from interrupt, restoring status and pc from the stack
+    fn rti(&mut self) {
+        self.dummy_read(STACK_BASE | self.sp as u16);
+        self.p = ProcStat::from_stack(self.pull_byte());
+        let pcl = self.pull_byte() as u16;
+        let pch = self.pull_byte() as u16;
+        self.pc = (pch << 8) | pcl;
+    }
+
+    /// push pc and status, disable interrupts and jump through the vector
+    /// the 65c02 also leaves decimal mode
+    fn interrupt(&mut self, vector: u16, brk: bool) {
+        self.push_byte((self.pc >> 8) as u8);
+        self.push_byte(self.pc as u8);
+        self.push_byte(self.p.to_stack(brk));
+        self.p.insert(ProcStat::I);
+        if self.variant.is_cmos() {
+            self.p.remove(ProcStat::D);
+        }
+        self.pc = self.read_word(vector);
+    }
+
+    /// push a byte onto the stack, wrapping within page one
+    fn push_byte(&mut self, data: u8) {
+        self.write(STACK_BASE | self.sp as u16, data);
+        if self.sp == 0x00 {
+            self.stack_fault(StackFault::Overflow { pc: self.pc });
+        }
+        self.sp = self.sp.wrapping_sub(1);
+    }
+
+    /// pull a byte from the stack, wrapping within page one
+    fn pull_byte(&mut self) -> u8 {
+        let wrapped = self.sp == 0xFF;
+        self.sp = self.sp.wrapping_add(1);
+        let data = self.read(STACK_BASE | self.sp as u16);
+        if wrapped {
+            self.stack_fault(StackFault::Underflow { pc: self.pc });
+        }
+        data
+    }
+
+    /// report a stack wrap to the diagnostic hook, if one is set
+    /// only once the access that wrapped has reached the bus, not while `tick` replays it
+    fn stack_fault(&mut self, fault: StackFault) {
+        if !self.tick.live {
+            return;
+        }
+        if let Some(hook) = self.on_stack_fault {
+            hook(&mut self.mem, fault);
+        }
+    }
+
+    /// no-op (do nothing)
+    fn nop(&mut self) {}
+
+    /* LOW POWER INSTRUCTIONS */
+
+    /// wait for an interrupt
+    fn wai(&mut self) {
+        self.dummy_read(self.pc);
+        self.waiting = true;
+    }
+
+    /// stop the clock until reset
+    fn stp(&mut self) {
+        self.dummy_read(self.pc);
+        self.stopped = true;
+    }
+
+    /* BIT MANIPULATION INSTRUCTIONS */
+
+    /// reset a bit of a zero page location
+    fn rmb(&mut self, bit: u8) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, |_, value| value & !(1 << bit));
+    }
+
+    /// set a bit of a zero page location
+    fn smb(&mut self, bit: u8) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, |_, value| value | (1 << bit));
+    }
+
+    /// branch if a bit of a zero page location is reset
+    fn bbr(&mut self, bit: u8) {
+        let value = self.read_bit_operand();
+        self.branch((value & (1 << bit)) == 0);
+    }
+
+    /// branch if a bit of a zero page location is set
+    fn bbs(&mut self, bit: u8) {
+        let value = self.read_bit_operand();
+        self.branch((value & (1 << bit)) != 0);
+    }
+
+    /// fetch the zero page operand of BBR or BBS and read it,
+    /// the location is read twice before the branch offset is fetched
+    fn read_bit_operand(&mut self) -> u8 {
+        let address = self.fetch_byte() as u16;
+        let value = self.read(address);
+        self.dummy_read(address);
+        value
+    }
+
+    /* UNDOCUMENTED INSTRUCTIONS */
+
+    /// undocumented no-op, the operand is fetched and read like a load would
+    /// and then dropped, so an absolute x indexed one still pays for a page cross
+    fn nop_operand(&mut self, mode: AddrMode) {
+        let address = match mode {
+            AddrMode::Immediate => {
+                self.fetch_byte();
+                return;
+            }
+            AddrMode::ZeroPage => self.fetch_byte() as u16,
+            AddrMode::ZeroPageX => self.addr_zpx(),
+            AddrMode::Absolute => self.fetch_word(),
+            AddrMode::AbsoluteX => self.addr_absx(),
+            _ => return,
+        };
+        self.read(address);
+    }
+
+    /// shift left then or with accumulator indirect, x index
+    fn slo_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.modify(address, Self::slo);
+    }
+
+    /// shift left then or with accumulator zero page
+    fn slo_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::slo);
+    }
+
+    /// shift left then or with accumulator absolute
+    fn slo_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::slo);
+    }
+
+    /// shift left then or with accumulator indirect, y index
+    fn slo_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.modify(address, Self::slo);
+    }
+
+    /// shift left then or with accumulator zero page, x index
+    fn slo_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::slo);
+    }
+
+    /// shift left then or with accumulator absolute, y index
+    fn slo_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.modify(address, Self::slo);
+    }
+
+    /// shift left then or with accumulator absolute, x index
+    fn slo_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::slo);
+    }
+
+    /// rotate left then and with accumulator indirect, x index
+    fn rla_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.modify(address, Self::rla);
+    }
+
+    /// rotate left then and with accumulator zero page
+    fn rla_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::rla);
+    }
+
+    /// rotate left then and with accumulator absolute
+    fn rla_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::rla);
+    }
+
+    /// rotate left then and with accumulator indirect, y index
+    fn rla_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.modify(address, Self::rla);
+    }
+
+    /// rotate left then and with accumulator zero page, x index
+    fn rla_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::rla);
+    }
+
+    /// rotate left then and with accumulator absolute, y index
+    fn rla_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.modify(address, Self::rla);
+    }
+
+    /// rotate left then and with accumulator absolute, x index
+    fn rla_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::rla);
+    }
+
+    /// shift right then exclusive or with accumulator indirect, x index
+    fn sre_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.modify(address, Self::sre);
+    }
+
+    /// shift right then exclusive or with accumulator zero page
+    fn sre_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::sre);
+    }
+
+    /// shift right then exclusive or with accumulator absolute
+    fn sre_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::sre);
+    }
+
+    /// shift right then exclusive or with accumulator indirect, y index
+    fn sre_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.modify(address, Self::sre);
+    }
+
+    /// shift right then exclusive or with accumulator zero page, x index
+    fn sre_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::sre);
+    }
+
+    /// shift right then exclusive or with accumulator absolute, y index
+    fn sre_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.modify(address, Self::sre);
+    }
+
+    /// shift right then exclusive or with accumulator absolute, x index
+    fn sre_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::sre);
+    }
+
+    /// rotate right then add with carry indirect, x index
+    fn rra_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.modify(address, Self::rra);
+    }
+
+    /// rotate right then add with carry zero page
+    fn rra_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::rra);
+    }
+
+    /// rotate right then add with carry absolute
+    fn rra_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::rra);
+    }
+
+    /// rotate right then add with carry indirect, y index
+    fn rra_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.modify(address, Self::rra);
+    }
+
+    /// rotate right then add with carry zero page, x index
+    fn rra_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::rra);
+    }
+
+    /// rotate right then add with carry absolute, y index
+    fn rra_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.modify(address, Self::rra);
+    }
+
+    /// rotate right then add with carry absolute, x index
+    fn rra_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::rra);
+    }
+
+    /// decrement then compare with accumulator indirect, x index
+    fn dcp_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.modify(address, Self::dcp);
+    }
+
+    /// decrement then compare with accumulator zero page
+    fn dcp_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::dcp);
+    }
+
+    /// decrement then compare with accumulator absolute
+    fn dcp_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::dcp);
+    }
+
+    /// decrement then compare with accumulator indirect, y index
+    fn dcp_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.modify(address, Self::dcp);
+    }
+
+    /// decrement then compare with accumulator zero page, x index
+    fn dcp_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::dcp);
+    }
+
+    /// decrement then compare with accumulator absolute, y index
+    fn dcp_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.modify(address, Self::dcp);
+    }
+
+    /// decrement then compare with accumulator absolute, x index
+    fn dcp_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::dcp);
+    }
+
+    /// increment then subtract with borrow indirect, x index
+    fn isc_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.modify(address, Self::isc);
+    }
+
+    /// increment then subtract with borrow zero page
+    fn isc_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.modify(address as u16, Self::isc);
+    }
+
+    /// increment then subtract with borrow absolute
+    fn isc_abs(&mut self) {
+        let address = self.fetch_word();
+        self.modify(address, Self::isc);
+    }
+
+    /// increment then subtract with borrow indirect, y index
+    fn isc_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        self.fix_index(address);
+        self.modify(address, Self::isc);
+    }
+
+    /// increment then subtract with borrow zero page, x index
+    fn isc_zpx(&mut self) {
+        let address = self.addr_zpx();
+        self.modify(address, Self::isc);
+    }
+
+    /// increment then subtract with borrow absolute, y index
+    fn isc_absy(&mut self) {
+        let address = self.addr_absy();
+        self.fix_index(address);
+        self.modify(address, Self::isc);
+    }
+
+    /// increment then subtract with borrow absolute, x index
+    fn isc_absx(&mut self) {
+        let address = self.addr_absx();
+        self.fix_index(address);
+        self.modify(address, Self::isc);
+    }
+
+    /// shift value left, then or the result into the accumulator
+    fn slo(&mut self, value: u8) -> u8 {
+        let result = self.asl(value);
+        self.a |= result;
+        self.set_flags();
+        result
+    }
+
+    /// rotate value left, then and the result into the accumulator
+    fn rla(&mut self, value: u8) -> u8 {
+        let result = self.rol(value);
+        self.a &= result;
+        self.set_flags();
+        result
+    }
+
+    /// shift value right, then exclusive or the result into the accumulator
+    fn sre(&mut self, value: u8) -> u8 {
+        let result = self.lsr(value);
+        self.a ^= result;
+        self.set_flags();
+        result
+    }
+
+    /// rotate value right, then add the result to the accumulator
+    /// with the carry shifted out by the rotate
+    fn rra(&mut self, value: u8) -> u8 {
+        let result = self.ror(value);
+        self.adc(result);
+        result
+    }
+
+    /// decrement value, then compare the accumulator with the result
+    fn dcp(&mut self, value: u8) -> u8 {
+        let result = value.wrapping_sub(1);
+        self.compare(self.a, result);
+        result
+    }
+
+    /// increment value, then subtract the result from the accumulator
+    fn isc(&mut self, value: u8) -> u8 {
+        let result = value.wrapping_add(1);
+        self.sbc(result);
+        result
+    }
+
+    /// store accumulator and x indirect, x index
+    fn sax_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        self.write(address, self.a & self.x);
+    }
+
+    /// store accumulator and x zero page
+    fn sax_zp(&mut self) {
+        let address = self.fetch_byte();
+        self.write(address as u16, self.a & self.x);
+    }
+
+    /// store accumulator and x absolute
+    fn sax_abs(&mut self) {
+        let address = self.fetch_word();
+        self.write(address, self.a & self.x);
+    }
+
+    /// store accumulator and x zero page, y index
+    fn sax_zpy(&mut self) {
+        let address = self.addr_zpy();
+        self.write(address, self.a & self.x);
+    }
+
+    /// load accumulator and x indirect, x index
+    fn lax_zpxi(&mut self) {
+        let address = self.addr_zpxi();
+        let value = self.read(address);
+        self.lax(value);
+    }
+
+    /// load accumulator and x zero page
+    fn lax_zp(&mut self) {
+        let address = self.fetch_byte();
+        let value = self.read(address as u16);
+        self.lax(value);
+    }
+
+    /// load accumulator and x immediate
+    /// unstable, the accumulator is mixed in through the magic constant
+    fn lax_im(&mut self) {
+        let value = self.fetch_byte();
+        self.lax((self.a | UNSTABLE_MAGIC) & value);
+    }
+
+    /// load accumulator and x absolute
+    fn lax_abs(&mut self) {
+        let address = self.fetch_word();
+        let value = self.read(address);
+        self.lax(value);
+    }
+
+    /// load accumulator and x indirect, y index
+    fn lax_zpyi(&mut self) {
+        let address = self.addr_zpyi();
+        let value = self.read(address);
+        self.lax(value);
+    }
+
+    /// load accumulator and x zero page, y index
+    fn lax_zpy(&mut self) {
+        let address = self.addr_zpy();
+        let value = self.read(address);
+        self.lax(value);
+    }
+
+    /// load accumulator and x absolute, y index
+    fn lax_absy(&mut self) {
+        let address = self.addr_absy();
+        let value = self.read(address);
+        self.lax(value);
+    }
+
+    /// load value into both the accumulator and x
+    fn lax(&mut self, value: u8) {
+        self.a = value;
+        self.x = value;
+        self.set_flags();
+    }
+
+    /// and accumulator with immediate, then copy the negative flag into carry
+    fn anc_im(&mut self) {
+        self.a &= self.fetch_byte();
+        self.set_flags();
+        self.set_carry_flag(self.p.contains(ProcStat::N));
+    }
+
+    /// and accumulator with immediate, then shift it right
+    fn alr_im(&mut self) {
+        let value = self.fetch_byte();
+        self.a = self.lsr(self.a & value);
+    }
+
+    /// and accumulator with immediate, then rotate it right
+    /// C and V come from bits 6 and 5 of the result as the adder sees them,
+    /// in decimal mode the nmos 6502 also applies a bcd fixup to each nibble
+    fn arr_im(&mut self) {
+        let value = self.a & self.fetch_byte();
+        let carry = self.p.contains(ProcStat::C);
+        let mut result = (value >> 1) | ((carry as u8) << 7);
+        self.set_result_flags(result);
+
+        if !self.decimal_mode() {
+            self.set_carry_flag((result & 0x40) > 0);
+            self.p.set(ProcStat::V, ((result >> 6) ^ (result >> 5)) & 0x01 > 0);
+            self.a = result;
+            return;
+        }
+
+        self.p.set(ProcStat::V, ((value ^ result) & 0x40) > 0);
+        if (value & 0x0F) + (value & 0x01) > 0x05 {
+            result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+        }
+        let fix_high = (value as u16 & 0xF0) + (value as u16 & 0x10) > 0x50;
+        if fix_high {
+            result = result.wrapping_add(0x60);
+        }
+        self.set_carry_flag(fix_high);
+        self.a = result;
+    }
+
+    /// and x with the accumulator and immediate into the accumulator
+    /// unstable, the accumulator is mixed in through the magic constant
+    fn xaa_im(&mut self) {
+        let value = self.fetch_byte();
+        self.a = (self.a | UNSTABLE_MAGIC) & self.x & value;
+        self.set_flags();
+    }
+
+    /// and accumulator with x, then subtract immediate without borrow into x
+    /// the flags are set like CMP
+    fn axs_im(&mut self) {
+        let value = self.fetch_byte();
+        let register = self.a & self.x;
+        self.compare(register, value);
+        self.x = register.wrapping_sub(value);
+    }
+
+    /// and memory with the stack pointer into accumulator, x and stack pointer absolute, y index
+    fn las_absy(&mut self) {
+        let address = self.addr_absy();
+        let value = self.read(address) & self.sp;
+        self.sp = value;
+        self.lax(value);
+    }
+
+    /// move accumulator and x into the stack pointer,
+    /// then store it and high byte + 1 absolute, y index
+    fn tas_absy(&mut self) {
+        self.sp = self.a & self.x;
+        let base = self.fetch_word();
+        self.store_and_high(base, self.y, self.sp);
+    }
+
+    /// store y and high byte + 1 absolute, x index
+    fn shy_absx(&mut self) {
+        let base = self.fetch_word();
+        self.store_and_high(base, self.x, self.y);
+    }
+
+    /// store x and high byte + 1 absolute, y index
+    fn shx_absy(&mut self) {
+        let base = self.fetch_word();
+        self.store_and_high(base, self.y, self.x);
+    }
+
+    /// store accumulator, x and high byte + 1 indirect, y index
+    fn ahx_zpyi(&mut self) {
+        let pointer = self.fetch_byte();
+        let base = self.read_zp_word(pointer);
+        self.store_and_high(base, self.y, self.a & self.x);
+    }
+
+    /// store accumulator, x and high byte + 1 absolute, y index
+    fn ahx_absy(&mut self) {
+        let base = self.fetch_word();
+        self.store_and_high(base, self.y, self.a & self.x);
+    }
+
+    /// store value anded with the high byte of base + 1 to base indexed
+    /// when the index crosses a page the stored value also replaces the high byte
+    /// of the address, these are unstable on real chips when a page is crossed
+    fn store_and_high(&mut self, base: u16, index: u8, value: u8) {
+        let address = self.index(base, index);
+        self.fix_index(address);
+        let value = value & ((base >> 8) as u8).wrapping_add(1);
+        let address = if self.page_crossed {
+            ((value as u16) << 8) | (address & 0x00FF)
+        } else {
+            address
+        };
+        self.write(address, value);
+    }
+}
@@ -0,0 +1,108 @@
+use core::fmt;
+
+/// why the cpu could not execute an instruction
+/// pc is the address of the opcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuError {
+    /// the opcode has no behaviour under the current `IllegalOpcodePolicy`
+    UnknownOpcode { pc: u16, opcode: u8 },
+    /// a KIL (JAM) opcode locked up the cpu, only a reset recovers
+    Jammed { pc: u16, opcode: u8 },
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CpuError::UnknownOpcode { pc, opcode } => {
+                write!(f, "unknown opcode 0x{:02X} at 0x{:04X}", opcode, pc)
+            }
+            CpuError::Jammed { pc, opcode } => {
+                write!(f, "cpu jammed by opcode 0x{:02X} at 0x{:04X}", opcode, pc)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CpuError {}
+
+/// what the cpu does with opcodes that are not documented instructions
+/// the reserved opcodes of the 65c02 parts are always no-ops, whatever the policy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalOpcodePolicy {
+    /// stop with `CpuError::UnknownOpcode`
+    #[default]
+    Trap,
+    /// skip the opcode and its operand like a NOP of the same length
+    Nop,
+    /// run the undocumented nmos behaviour, KIL opcodes jam the cpu
+    Execute,
+}
+
+/// why a program image could not be loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadError {
+    /// len bytes starting at address would run past 0xFFFF
+    OutOfBounds { address: u16, len: usize },
+    /// the byte written at address did not read back, it is not RAM
+    NotWritable { address: u16 },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LoadError::OutOfBounds { address, len } => write!(
+                f,
+                "{} bytes loaded at 0x{:04X} would run past 0xFFFF",
+                len, address
+            ),
+            LoadError::NotWritable { address } => {
+                write!(f, "0x{:04X} did not read back what was loaded", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// why a memory map could not be built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapError {
+    /// a region ends before it starts
+    EmptyRegion { start: u16 },
+    /// two regions claim the same address
+    Overlap { address: u16 },
+    /// a rom image is empty or larger than its region
+    RomSize { start: u16, len: usize },
+    /// a banked window has no banks, or its image is not a whole number of banks
+    BankSize { start: u16, len: usize },
+    /// a mirror points at an empty range, or one not backed by ram, rom or a device
+    MirrorTarget { start: u16 },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MapError::EmptyRegion { start } => {
+                write!(f, "region at 0x{:04X} ends before it starts", start)
+            }
+            MapError::Overlap { address } => {
+                write!(f, "more than one region is mapped at 0x{:04X}", address)
+            }
+            MapError::RomSize { start, len } => {
+                write!(f, "rom of {} bytes does not fit the region at 0x{:04X}", len, start)
+            }
+            MapError::BankSize { start, len } => write!(
+                f,
+                "{} bytes are not a whole number of banks for the window at 0x{:04X}",
+                len, start
+            ),
+            MapError::MirrorTarget { start } => write!(
+                f,
+                "mirror at 0x{:04X} does not point at ram, rom or a device",
+                start
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
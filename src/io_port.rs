@@ -0,0 +1,60 @@
+/*
+    6510 i/o port
+    the 6510 has an 8-bit port built in, its data direction register is at 0x0000
+    and the port itself at 0x0001. both are inside the cpu, so the bus never sees
+    these accesses. on the commodore 64 the low three pins select which of ram,
+    basic, kernal, character rom and i/o are visible
+    - https://www.c64-wiki.com/wiki/Zeropage
+    - https://www.c64-wiki.com/wiki/Bank_Switching
+*/
+
+/// address of the data direction register
+pub const DDR_ADDRESS: u16 = 0x0000;
+/// address of the port register
+pub const PORT_ADDRESS: u16 = 0x0001;
+
+/// the i/o port of the 6510
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoPort {
+    /// data direction register, a set bit makes that pin an output
+    pub direction: u8,
+    /// output latch, driven onto the pins set as outputs
+    pub data: u8,
+    /// level of the pins set as inputs, high when nothing drives them
+    pub input: u8,
+}
+
+impl Default for IoPort {
+    fn default() -> Self {
+        IoPort {
+            direction: 0,
+            data: 0,
+            input: 0xFF,
+        }
+    }
+}
+
+impl IoPort {
+    /// level of every pin, outputs from the latch and inputs from outside
+    pub fn pins(&self) -> u8 {
+        (self.data & self.direction) | (self.input & !self.direction)
+    }
+
+    /// read the register at address, either DDR_ADDRESS or PORT_ADDRESS
+    pub fn read(&self, address: u16) -> u8 {
+        if address == DDR_ADDRESS {
+            self.direction
+        } else {
+            self.pins()
+        }
+    }
+
+    /// write the register at address, either DDR_ADDRESS or PORT_ADDRESS
+    pub fn write(&mut self, address: u16, data: u8) {
+        if address == DDR_ADDRESS {
+            self.direction = data;
+        } else {
+            self.data = data;
+        }
+    }
+}
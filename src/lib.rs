@@ -0,0 +1,10 @@
+pub mod bus;
+pub mod cpu;
+pub mod error;
+pub mod io_port;
+pub mod map;
+pub mod mem;
+pub mod op_codes;
+pub mod op_table;
+pub mod proc_stat;
+pub mod variant;
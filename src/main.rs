@@ -1,23 +1,21 @@
-mod cpu;
-mod mem;
-mod op_codes;
-mod proc_stat;
-
-use cpu::Cpu;
-use op_codes::*;
+use q_6502::{cpu::Cpu, op_codes::*};
 
 fn main() {
-    let mut cpu = Cpu::new().reset(None);
-    // would overflow if ran from reset vector
-    // set PC to lower address
-    cpu.pc = 0xFFF0;
-    // Load a dummy program into mem
-    cpu.mem.data[0xFFF0] = LDX_ABS;
-    cpu.mem.data[0xFFF1] = 0x80;
-    cpu.mem.data[0xFFF2] = 0x44; // 0x4480
-    cpu.mem.data[0x4480] = 0x37;
-    cpu.mem.data[0xFFF3] = NOP;
+    let mut cpu = Cpu::new();
+    // Load a dummy program into mem and start it from the reset vector
+    cpu.load_segments(
+        &[
+            (0x0200, &[LDX_ABS, 0x80, 0x44, NOP]), // ldx 0x4480
+            (0x4480, &[0x37]),
+        ],
+        Some(0x0200),
+    )
+    .expect("program fits in memory");
+    cpu.reset();
 
-    cpu.execute();
+    // run through the NOP
+    if let Err(error) = cpu.run_until(|cpu| cpu.pc == 0x0204) {
+        println!("error: {}", error);
+    }
     cpu.debug_print();
 }
@@ -0,0 +1,391 @@
+/*
+    address decoder
+    a memory map is built from regions of ram, rom, mirrors, devices and
+    banked windows. anything left unmapped is open bus: reads return the last
+    value that was on the data bus, writes go nowhere.
+    a banked window shows one of several equally sized banks at a time,
+    the bank is picked by writing its number to the window's select register
+    - https://www.nesdev.org/wiki/Open_bus_behavior
+*/
+
+use core::{fmt, ops::RangeInclusive};
+
+use crate::{
+    bus::{BankSelection, Bus},
+    error::MapError,
+};
+
+/// what sits behind a region of the address space
+enum Backing {
+    Ram(Vec<u8>),
+    /// repeated through the region if it is smaller
+    Rom(Vec<u8>),
+    /// re-decoded at target, repeating every len bytes
+    Mirror { target: u16, len: u32 },
+    /// sees addresses relative to the start of the region
+    Device(Box<dyn Bus>),
+    /// one bank of data is visible at a time
+    Banked(Banks),
+}
+
+/// the banks behind a banked window
+struct Banks {
+    /// every bank back to back
+    data: Vec<u8>,
+    /// size of one bank, the size of the window
+    bank_size: usize,
+    selected: usize,
+    /// writing n here selects bank n, modulo the number of banks
+    register: u16,
+    /// ram banks can be written, writes to rom banks are dropped
+    writable: bool,
+}
+
+impl Banks {
+    fn count(&self) -> usize {
+        self.data.len() / self.bank_size
+    }
+}
+
+struct Region {
+    start: u16,
+    end: u16,
+    backing: Backing,
+}
+
+impl fmt::Debug for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{:04X}-0x{:04X} ", self.start, self.end)?;
+        match &self.backing {
+            Backing::Ram(data) => write!(f, "ram ({} bytes)", data.len()),
+            Backing::Rom(data) => write!(f, "rom ({} bytes)", data.len()),
+            Backing::Mirror { target, len } => {
+                write!(f, "mirror of 0x{:04X}-0x{:04X}", target, *target as u32 + len - 1)
+            }
+            Backing::Device(_) => write!(f, "device"),
+            Backing::Banked(banks) => write!(
+                f,
+                "banked {}, bank {} of {} (select 0x{:04X})",
+                if banks.writable { "ram" } else { "rom" },
+                banks.selected,
+                banks.count(),
+                banks.register
+            ),
+        }
+    }
+}
+
+/// a bus made of ram, rom, mirrored and device regions
+/// create one with `MemoryMap::builder`
+pub struct MemoryMap {
+    /// sorted by start address, never overlapping
+    regions: Vec<Region>,
+    /// last value on the data bus, returned by unmapped reads
+    data_bus: u8,
+    /// called with the address and data of every write to rom,
+    /// writes to rom are silently dropped when this is not set
+    pub on_rom_write: Option<fn(u16, u8)>,
+}
+
+impl fmt::Debug for MemoryMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemoryMap")
+            .field("regions", &self.regions)
+            .field("data_bus", &self.data_bus)
+            .finish()
+    }
+}
+
+impl MemoryMap {
+    /// start describing a memory map
+    pub fn builder() -> MapBuilder {
+        MapBuilder::default()
+    }
+
+    /// find the region that address decodes to
+    fn find(&self, address: u16) -> Option<usize> {
+        let index = self.regions.partition_point(|region| region.start <= address);
+        let index = index.checked_sub(1)?;
+        (address <= self.regions[index].end).then_some(index)
+    }
+
+    /// find the ram, rom or device region behind address, following a mirror
+    /// returns the region and the address after mirroring
+    fn resolve(&self, address: u16) -> Option<(usize, u16)> {
+        let index = self.find(address)?;
+        let region = &self.regions[index];
+        match region.backing {
+            Backing::Mirror { target, len } => {
+                let offset = (address - region.start) as u32 % len;
+                let address = target + offset as u16;
+                Some((self.find(address)?, address))
+            }
+            _ => Some((index, address)),
+        }
+    }
+
+    /// latch data into every banked window selected by register
+    /// returns true if address is a select register
+    fn select_bank(&mut self, register: u16, data: u8) -> bool {
+        let mut selected = false;
+        for region in &mut self.regions {
+            if let Backing::Banked(banks) = &mut region.backing {
+                if banks.register == register {
+                    banks.selected = data as usize % banks.count();
+                    selected = true;
+                }
+            }
+        }
+        selected
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: u16) -> u8 {
+        if let Some((index, address)) = self.resolve(address) {
+            let region = &mut self.regions[index];
+            let offset = (address - region.start) as usize;
+            self.data_bus = match &mut region.backing {
+                Backing::Ram(data) => data[offset],
+                Backing::Rom(data) => data[offset % data.len()],
+                Backing::Device(device) => device.read(offset as u16),
+                Backing::Banked(banks) => banks.data[banks.selected * banks.bank_size + offset],
+                Backing::Mirror { .. } => self.data_bus,
+            };
+        }
+        self.data_bus
+    }
+
+    /// a write to a bank select register is taken by the latch
+    /// and does not reach whatever is mapped at that address
+    fn write(&mut self, address: u16, data: u8) {
+        self.data_bus = data;
+        if self.select_bank(address, data) {
+            return;
+        }
+
+        if let Some((index, resolved)) = self.resolve(address) {
+            let region = &mut self.regions[index];
+            let offset = (resolved - region.start) as usize;
+            match &mut region.backing {
+                Backing::Ram(ram) => ram[offset] = data,
+                Backing::Rom(_) => {
+                    if let Some(hook) = self.on_rom_write {
+                        hook(address, data);
+                    }
+                }
+                Backing::Device(device) => device.write(offset as u16, data),
+                Backing::Banked(banks) if banks.writable => {
+                    banks.data[banks.selected * banks.bank_size + offset] = data
+                }
+                Backing::Banked(_) => {
+                    if let Some(hook) = self.on_rom_write {
+                        hook(address, data);
+                    }
+                }
+                Backing::Mirror { .. } => {}
+            }
+        }
+    }
+
+    /// what a read would return, devices are asked to peek themselves
+    fn peek(&self, address: u16) -> Option<u8> {
+        let Some((index, address)) = self.resolve(address) else {
+            return Some(self.data_bus);
+        };
+        let region = &self.regions[index];
+        let offset = (address - region.start) as usize;
+        match &region.backing {
+            Backing::Ram(data) => Some(data[offset]),
+            Backing::Rom(data) => Some(data[offset % data.len()]),
+            Backing::Device(device) => device.peek(offset as u16),
+            Backing::Banked(banks) => {
+                Some(banks.data[banks.selected * banks.bank_size + offset])
+            }
+            Backing::Mirror { .. } => Some(self.data_bus),
+        }
+    }
+
+    fn bank_selection(&self) -> Vec<BankSelection> {
+        self.regions
+            .iter()
+            .filter_map(|region| match &region.backing {
+                Backing::Banked(banks) => Some(BankSelection {
+                    start: region.start,
+                    end: region.end,
+                    bank: banks.selected,
+                    count: banks.count(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// describes a memory map region by region
+/// ranges are inclusive, anything not covered is open bus
+#[derive(Default)]
+pub struct MapBuilder {
+    regions: Vec<Region>,
+    /// the first mistake made while describing the map
+    error: Option<MapError>,
+}
+
+impl MapBuilder {
+    /// map zero-filled ram over range
+    pub fn ram(self, range: RangeInclusive<u16>) -> Self {
+        let len = range_len(&range);
+        self.region(range, Backing::Ram(vec![0; len as usize]))
+    }
+
+    /// map read-only data over range, an image smaller than the range
+    /// is repeated through it
+    pub fn rom(mut self, range: RangeInclusive<u16>, data: impl Into<Vec<u8>>) -> Self {
+        let data = data.into();
+        if data.is_empty() || data.len() as u32 > range_len(&range) {
+            self.fail(MapError::RomSize {
+                start: *range.start(),
+                len: data.len(),
+            });
+            return self;
+        }
+        self.region(range, Backing::Rom(data))
+    }
+
+    /// make range repeat target, e.g. 2K of ram at 0x0000-0x07FF
+    /// mirrored through 0x0800-0x1FFF
+    pub fn mirror(mut self, range: RangeInclusive<u16>, target: RangeInclusive<u16>) -> Self {
+        if target.is_empty() {
+            self.fail(MapError::MirrorTarget {
+                start: *range.start(),
+            });
+            return self;
+        }
+        let len = range_len(&target);
+        self.region(
+            range,
+            Backing::Mirror {
+                target: *target.start(),
+                len,
+            },
+        )
+    }
+
+    /// map a device over range, it sees addresses relative to the start of the range
+    pub fn device(self, range: RangeInclusive<u16>, device: impl Bus + 'static) -> Self {
+        self.region(range, Backing::Device(Box::new(device)))
+    }
+
+    /// map a window over range that shows one of count banks of zero-filled ram
+    /// writing n to register selects bank n, bank 0 is selected at first
+    pub fn banked_ram(self, range: RangeInclusive<u16>, count: usize, register: u16) -> Self {
+        let bank_size = range_len(&range) as usize;
+        self.banked(range, vec![0; bank_size * count], register, true)
+    }
+
+    /// map a window over range that shows one bank of a rom image at a time
+    /// the image is split into banks the size of the window,
+    /// writing n to register selects bank n, bank 0 is selected at first
+    pub fn banked_rom(
+        self,
+        range: RangeInclusive<u16>,
+        image: impl Into<Vec<u8>>,
+        register: u16,
+    ) -> Self {
+        self.banked(range, image.into(), register, false)
+    }
+
+    /// check the regions and build the map
+    pub fn build(mut self) -> Result<MemoryMap, MapError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        self.regions.sort_by_key(|region| region.start);
+        for pair in self.regions.windows(2) {
+            if pair[1].start <= pair[0].end {
+                return Err(MapError::Overlap {
+                    address: pair[1].start,
+                });
+            }
+        }
+
+        let map = MemoryMap {
+            regions: self.regions,
+            data_bus: 0,
+            on_rom_write: None,
+        };
+
+        // every address a mirror can reach must be backed by something real
+        for region in &map.regions {
+            if let Backing::Mirror { target, len } = region.backing {
+                let last = target as u32 + len - 1;
+                let backed = last <= 0xFFFF
+                    && (target as u32..=last).all(|address| {
+                        map.find(address as u16).is_some_and(|index| {
+                            !matches!(map.regions[index].backing, Backing::Mirror { .. })
+                        })
+                    });
+                if !backed {
+                    return Err(MapError::MirrorTarget {
+                        start: region.start,
+                    });
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
+    fn banked(
+        mut self,
+        range: RangeInclusive<u16>,
+        data: Vec<u8>,
+        register: u16,
+        writable: bool,
+    ) -> Self {
+        let bank_size = range_len(&range) as usize;
+        if bank_size > 0 && (data.is_empty() || !data.len().is_multiple_of(bank_size)) {
+            self.fail(MapError::BankSize {
+                start: *range.start(),
+                len: data.len(),
+            });
+            return self;
+        }
+
+        let banks = Banks {
+            data,
+            bank_size,
+            selected: 0,
+            register,
+            writable,
+        };
+        self.region(range, Backing::Banked(banks))
+    }
+
+    fn region(mut self, range: RangeInclusive<u16>, backing: Backing) -> Self {
+        if range.is_empty() {
+            self.fail(MapError::EmptyRegion {
+                start: *range.start(),
+            });
+            return self;
+        }
+
+        self.regions.push(Region {
+            start: *range.start(),
+            end: *range.end(),
+            backing,
+        });
+        self
+    }
+
+    /// remember only the first error
+    fn fail(&mut self, error: MapError) {
+        self.error.get_or_insert(error);
+    }
+}
+
+/// number of addresses in an inclusive range
+fn range_len(range: &RangeInclusive<u16>) -> u32 {
+    (*range.end() as u32 + 1).saturating_sub(*range.start() as u32)
+}
@@ -1,3 +1,5 @@
+use crate::bus::Bus;
+
 pub const MAX_MEM: usize = 1024 * 64;
 
 #[derive(Debug, Clone)]
@@ -12,10 +14,10 @@ impl Default for Memory {
 }
 
 impl Memory {
-    /// write a word (2 bytes) to memory
+    /// write a word (2 bytes) to memory, wrapping at the end of memory
     pub fn write_word(&mut self, address: usize, data: u16) {
         self.write_byte(address, (data & 0xFF) as u8);
-        self.write_byte(address + 1, (data >> 8) as u8);
+        self.write_byte((address + 1) % MAX_MEM, (data >> 8) as u8);
     }
 
     /// write a byte to memory
@@ -28,10 +30,25 @@ impl Memory {
         self.data[address]
     }
 
-    /// read a word (2 bytes) from memory
+    /// read a word (2 bytes) from memory, wrapping at the end of memory
     pub fn read_word(&mut self, address: usize) -> u16 {
         let mut data = self.read_byte(address) as u16;
-        data |= u16::from(self.read_byte(address + 1)) << 8;
+        data |= u16::from(self.read_byte((address + 1) % MAX_MEM)) << 8;
         data
     }
-}
\ No newline at end of file
+}
+
+/// flat 64K of ram, the default bus
+impl Bus for Memory {
+    fn read(&mut self, address: u16) -> u8 {
+        self.read_byte(address as usize)
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        self.write_byte(address as usize, data)
+    }
+
+    fn peek(&self, address: u16) -> Option<u8> {
+        Some(self.data[address as usize])
+    }
+}
@@ -18,7 +18,7 @@ pub const LDA_ZPYI: u8 = 0xB1;
 /// load x index immediate
 pub const LDX_IM: u8 = 0xA2;
 /// load x index absolute
-pub const LDX_ABS: u8 = 0xA3;
+pub const LDX_ABS: u8 = 0xAE;
 /// load x index y indexed absolute
 pub const LDX_ABSY: u8 = 0xBE;
 /// load x index zero page
@@ -37,6 +37,46 @@ pub const LDY_ZP: u8 = 0xA4;
 /// load y index x indexed zero page
 pub const LDY_ZPX: u8 = 0xB4;
 
+/// store accumulator absolute
+pub const STA_ABS: u8 = 0x8D;
+/// store accumulator absolute x indexed
+pub const STA_ABSX: u8 = 0x9D;
+/// store accumulator absolute y indexed
+pub const STA_ABSY: u8 = 0x99;
+/// store accumulator zero page
+pub const STA_ZP: u8 = 0x85;
+/// store accumulator zero page, x index
+pub const STA_ZPX: u8 = 0x95;
+/// store accumulator zero page x indexed indirect
+pub const STA_ZPXI: u8 = 0x81;
+/// store accumulator zero page y indexed indirect
+pub const STA_ZPYI: u8 = 0x91;
+
+/// store x index absolute
+pub const STX_ABS: u8 = 0x8E;
+/// store x index zero page
+pub const STX_ZP: u8 = 0x86;
+/// store x index y indexed zero page
+pub const STX_ZPY: u8 = 0x96;
+
+/// store y index absolute
+pub const STY_ABS: u8 = 0x8C;
+/// store y index zero page
+pub const STY_ZP: u8 = 0x84;
+/// store y index x indexed zero page
+pub const STY_ZPX: u8 = 0x94;
+
+/// arithmetic shift left accumulator
+pub const ASL_ACC: u8 = 0x0A;
+/// arithmetic shift left absolute
+pub const ASL_ABS: u8 = 0x0E;
+/// arithmetic shift left zero page
+pub const ASL_ZP: u8 = 0x06;
+/// arithmetic shift left absolute x indexed
+pub const ASL_ABSX: u8 = 0x1E;
+/// arithmetic shift left zero page x indexed
+pub const ASL_ZPX: u8 = 0x16;
+
 /// logical shift right accumulator
 pub const LSR_ACC: u8 = 0x4A;
 /// logical shift right absolute
@@ -48,6 +88,55 @@ pub const LSR_ABSX: u8 = 0x5E;
 /// logical shift right zero page x indexed
 pub const LSR_ZPX: u8 = 0x56;
 
+/// rotate left accumulator
+pub const ROL_ACC: u8 = 0x2A;
+/// rotate left absolute
+pub const ROL_ABS: u8 = 0x2E;
+/// rotate left zero page
+pub const ROL_ZP: u8 = 0x26;
+/// rotate left absolute x indexed
+pub const ROL_ABSX: u8 = 0x3E;
+/// rotate left zero page x indexed
+pub const ROL_ZPX: u8 = 0x36;
+
+/// rotate right accumulator
+pub const ROR_ACC: u8 = 0x6A;
+/// rotate right absolute
+pub const ROR_ABS: u8 = 0x6E;
+/// rotate right zero page
+pub const ROR_ZP: u8 = 0x66;
+/// rotate right absolute x indexed
+pub const ROR_ABSX: u8 = 0x7E;
+/// rotate right zero page x indexed
+pub const ROR_ZPX: u8 = 0x76;
+
+/// increment memory absolute
+pub const INC_ABS: u8 = 0xEE;
+/// increment memory zero page
+pub const INC_ZP: u8 = 0xE6;
+/// increment memory absolute x indexed
+pub const INC_ABSX: u8 = 0xFE;
+/// increment memory zero page x indexed
+pub const INC_ZPX: u8 = 0xF6;
+
+/// decrement memory absolute
+pub const DEC_ABS: u8 = 0xCE;
+/// decrement memory zero page
+pub const DEC_ZP: u8 = 0xC6;
+/// decrement memory absolute x indexed
+pub const DEC_ABSX: u8 = 0xDE;
+/// decrement memory zero page x indexed
+pub const DEC_ZPX: u8 = 0xD6;
+
+/// increment x index
+pub const INX: u8 = 0xE8;
+/// increment y index
+pub const INY: u8 = 0xC8;
+/// decrement x index
+pub const DEX: u8 = 0xCA;
+/// decrement y index
+pub const DEY: u8 = 0x88;
+
 /// push accumulator on the stack
 pub const PHA: u8 = 0x48;
 /// push processor status on the stack
@@ -108,6 +197,93 @@ pub const EORA_ZPXI: u8 = 0x41;
 /// exclusive or accumulator zero page indirect y indexed
 pub const EORA_ZPYI: u8 = 0x51;
 
+/// add with carry immediate
+pub const ADC_IM: u8 = 0x69;
+/// add with carry absolute
+pub const ADC_ABS: u8 = 0x6D;
+/// add with carry x indexed absolute
+pub const ADC_ABSX: u8 = 0x7D;
+/// add with carry y indexed absolute
+pub const ADC_ABSY: u8 = 0x79;
+/// add with carry zero page
+pub const ADC_ZP: u8 = 0x65;
+/// add with carry x indexed zero page
+pub const ADC_ZPX: u8 = 0x75;
+/// add with carry x indexed zero page indirect
+pub const ADC_ZPXI: u8 = 0x61;
+/// add with carry zero page indirect y indexed
+pub const ADC_ZPYI: u8 = 0x71;
+
+/// subtract with borrow immediate
+pub const SBC_IM: u8 = 0xE9;
+/// subtract with borrow absolute
+pub const SBC_ABS: u8 = 0xED;
+/// subtract with borrow x indexed absolute
+pub const SBC_ABSX: u8 = 0xFD;
+/// subtract with borrow y indexed absolute
+pub const SBC_ABSY: u8 = 0xF9;
+/// subtract with borrow zero page
+pub const SBC_ZP: u8 = 0xE5;
+/// subtract with borrow x indexed zero page
+pub const SBC_ZPX: u8 = 0xF5;
+/// subtract with borrow x indexed zero page indirect
+pub const SBC_ZPXI: u8 = 0xE1;
+/// subtract with borrow zero page indirect y indexed
+pub const SBC_ZPYI: u8 = 0xF1;
+
+/// compare accumulator immediate
+pub const CMP_IM: u8 = 0xC9;
+/// compare accumulator absolute
+pub const CMP_ABS: u8 = 0xCD;
+/// compare accumulator x indexed absolute
+pub const CMP_ABSX: u8 = 0xDD;
+/// compare accumulator y indexed absolute
+pub const CMP_ABSY: u8 = 0xD9;
+/// compare accumulator zero page
+pub const CMP_ZP: u8 = 0xC5;
+/// compare accumulator x indexed zero page
+pub const CMP_ZPX: u8 = 0xD5;
+/// compare accumulator x indexed zero page indirect
+pub const CMP_ZPXI: u8 = 0xC1;
+/// compare accumulator zero page indirect y indexed
+pub const CMP_ZPYI: u8 = 0xD1;
+
+/// compare x index immediate
+pub const CPX_IM: u8 = 0xE0;
+/// compare x index absolute
+pub const CPX_ABS: u8 = 0xEC;
+/// compare x index zero page
+pub const CPX_ZP: u8 = 0xE4;
+
+/// compare y index immediate
+pub const CPY_IM: u8 = 0xC0;
+/// compare y index absolute
+pub const CPY_ABS: u8 = 0xCC;
+/// compare y index zero page
+pub const CPY_ZP: u8 = 0xC4;
+
+/// test bits absolute
+pub const BIT_ABS: u8 = 0x2C;
+/// test bits zero page
+pub const BIT_ZP: u8 = 0x24;
+
+/// branch on result plus
+pub const BPL: u8 = 0x10;
+/// branch on result minus
+pub const BMI: u8 = 0x30;
+/// branch on overflow clear
+pub const BVC: u8 = 0x50;
+/// branch on overflow set
+pub const BVS: u8 = 0x70;
+/// branch on carry clear
+pub const BCC: u8 = 0x90;
+/// branch on carry set
+pub const BCS: u8 = 0xB0;
+/// branch on result not zero
+pub const BNE: u8 = 0xD0;
+/// branch on result zero
+pub const BEQ: u8 = 0xF0;
+
 /// transfer accumulator to x index
 pub const TAX: u8 = 0xAA;
 /// transfer accumulator to y index
@@ -121,9 +297,316 @@ pub const TXS: u8 = 0x9A;
 /// transfer stack pointer to x index
 pub const TSX: u8 = 0xBA;
 
+/// clear carry
+pub const CLC: u8 = 0x18;
+/// set carry
+pub const SEC: u8 = 0x38;
+/// clear interrupt disable
+pub const CLI: u8 = 0x58;
+/// set interrupt disable
+pub const SEI: u8 = 0x78;
+/// clear decimal
+pub const CLD: u8 = 0xD8;
+/// set decimal
+pub const SED: u8 = 0xF8;
+/// clear overflow
+pub const CLV: u8 = 0xB8;
+
+/// force break
+pub const BRK: u8 = 0x00;
+/// return from interrupt
+pub const RTI: u8 = 0x40;
+
 /// no-op
 pub const NOP: u8 = 0xEA;
+/// jump absolute
+pub const JMP_ABS: u8 = 0x4C;
+/// jump indirect
+pub const JMP_IND: u8 = 0x6C;
 /// jump subroutine
 pub const JSR: u8 = 0x20;
 /// return from subroutine
-pub const RTS: u8 = 0x60;
\ No newline at end of file
+pub const RTS: u8 = 0x60;
+
+/* UNDOCUMENTED NMOS OPCODES */
+
+/// shift left then or with accumulator zero page x indexed indirect
+pub const SLO_ZPXI: u8 = 0x03;
+/// shift left then or with accumulator zero page
+pub const SLO_ZP: u8 = 0x07;
+/// shift left then or with accumulator absolute
+pub const SLO_ABS: u8 = 0x0F;
+/// shift left then or with accumulator zero page y indexed indirect
+pub const SLO_ZPYI: u8 = 0x13;
+/// shift left then or with accumulator zero page, x index
+pub const SLO_ZPX: u8 = 0x17;
+/// shift left then or with accumulator absolute y indexed
+pub const SLO_ABSY: u8 = 0x1B;
+/// shift left then or with accumulator absolute x indexed
+pub const SLO_ABSX: u8 = 0x1F;
+
+/// rotate left then and with accumulator zero page x indexed indirect
+pub const RLA_ZPXI: u8 = 0x23;
+/// rotate left then and with accumulator zero page
+pub const RLA_ZP: u8 = 0x27;
+/// rotate left then and with accumulator absolute
+pub const RLA_ABS: u8 = 0x2F;
+/// rotate left then and with accumulator zero page y indexed indirect
+pub const RLA_ZPYI: u8 = 0x33;
+/// rotate left then and with accumulator zero page, x index
+pub const RLA_ZPX: u8 = 0x37;
+/// rotate left then and with accumulator absolute y indexed
+pub const RLA_ABSY: u8 = 0x3B;
+/// rotate left then and with accumulator absolute x indexed
+pub const RLA_ABSX: u8 = 0x3F;
+
+/// shift right then exclusive or with accumulator zero page x indexed indirect
+pub const SRE_ZPXI: u8 = 0x43;
+/// shift right then exclusive or with accumulator zero page
+pub const SRE_ZP: u8 = 0x47;
+/// shift right then exclusive or with accumulator absolute
+pub const SRE_ABS: u8 = 0x4F;
+/// shift right then exclusive or with accumulator zero page y indexed indirect
+pub const SRE_ZPYI: u8 = 0x53;
+/// shift right then exclusive or with accumulator zero page, x index
+pub const SRE_ZPX: u8 = 0x57;
+/// shift right then exclusive or with accumulator absolute y indexed
+pub const SRE_ABSY: u8 = 0x5B;
+/// shift right then exclusive or with accumulator absolute x indexed
+pub const SRE_ABSX: u8 = 0x5F;
+
+/// rotate right then add with carry zero page x indexed indirect
+pub const RRA_ZPXI: u8 = 0x63;
+/// rotate right then add with carry zero page
+pub const RRA_ZP: u8 = 0x67;
+/// rotate right then add with carry absolute
+pub const RRA_ABS: u8 = 0x6F;
+/// rotate right then add with carry zero page y indexed indirect
+pub const RRA_ZPYI: u8 = 0x73;
+/// rotate right then add with carry zero page, x index
+pub const RRA_ZPX: u8 = 0x77;
+/// rotate right then add with carry absolute y indexed
+pub const RRA_ABSY: u8 = 0x7B;
+/// rotate right then add with carry absolute x indexed
+pub const RRA_ABSX: u8 = 0x7F;
+
+/// decrement then compare with accumulator zero page x indexed indirect
+pub const DCP_ZPXI: u8 = 0xC3;
+/// decrement then compare with accumulator zero page
+pub const DCP_ZP: u8 = 0xC7;
+/// decrement then compare with accumulator absolute
+pub const DCP_ABS: u8 = 0xCF;
+/// decrement then compare with accumulator zero page y indexed indirect
+pub const DCP_ZPYI: u8 = 0xD3;
+/// decrement then compare with accumulator zero page, x index
+pub const DCP_ZPX: u8 = 0xD7;
+/// decrement then compare with accumulator absolute y indexed
+pub const DCP_ABSY: u8 = 0xDB;
+/// decrement then compare with accumulator absolute x indexed
+pub const DCP_ABSX: u8 = 0xDF;
+
+/// increment then subtract with borrow zero page x indexed indirect
+pub const ISC_ZPXI: u8 = 0xE3;
+/// increment then subtract with borrow zero page
+pub const ISC_ZP: u8 = 0xE7;
+/// increment then subtract with borrow absolute
+pub const ISC_ABS: u8 = 0xEF;
+/// increment then subtract with borrow zero page y indexed indirect
+pub const ISC_ZPYI: u8 = 0xF3;
+/// increment then subtract with borrow zero page, x index
+pub const ISC_ZPX: u8 = 0xF7;
+/// increment then subtract with borrow absolute y indexed
+pub const ISC_ABSY: u8 = 0xFB;
+/// increment then subtract with borrow absolute x indexed
+pub const ISC_ABSX: u8 = 0xFF;
+
+/// store accumulator and x zero page x indexed indirect
+pub const SAX_ZPXI: u8 = 0x83;
+/// store accumulator and x zero page
+pub const SAX_ZP: u8 = 0x87;
+/// store accumulator and x absolute
+pub const SAX_ABS: u8 = 0x8F;
+/// store accumulator and x zero page, y index
+pub const SAX_ZPY: u8 = 0x97;
+
+/// load accumulator and x zero page x indexed indirect
+pub const LAX_ZPXI: u8 = 0xA3;
+/// load accumulator and x zero page
+pub const LAX_ZP: u8 = 0xA7;
+/// load accumulator and x immediate
+pub const LAX_IM: u8 = 0xAB;
+/// load accumulator and x absolute
+pub const LAX_ABS: u8 = 0xAF;
+/// load accumulator and x zero page y indexed indirect
+pub const LAX_ZPYI: u8 = 0xB3;
+/// load accumulator and x zero page, y index
+pub const LAX_ZPY: u8 = 0xB7;
+/// load accumulator and x absolute y indexed
+pub const LAX_ABSY: u8 = 0xBF;
+
+/// and accumulator with immediate, copying N into carry
+pub const ANC_IM: u8 = 0x0B;
+/// and accumulator with immediate, copying N into carry (duplicate)
+pub const ANC_IM_ALT: u8 = 0x2B;
+/// and accumulator with immediate then shift right
+pub const ALR_IM: u8 = 0x4B;
+/// and accumulator with immediate then rotate right
+pub const ARR_IM: u8 = 0x6B;
+/// and x with accumulator and immediate into accumulator (unstable)
+pub const XAA_IM: u8 = 0x8B;
+/// and accumulator with x then subtract immediate into x
+pub const AXS_IM: u8 = 0xCB;
+/// subtract with borrow immediate (duplicate of SBC_IM)
+pub const SBC_IM_ALT: u8 = 0xEB;
+
+/// and memory with stack pointer into accumulator, x and stack pointer absolute y indexed
+pub const LAS_ABSY: u8 = 0xBB;
+/// store accumulator and x into stack pointer, then store it and high byte + 1 absolute y indexed
+pub const TAS_ABSY: u8 = 0x9B;
+/// store y and high byte + 1 absolute x indexed
+pub const SHY_ABSX: u8 = 0x9C;
+/// store x and high byte + 1 absolute y indexed
+pub const SHX_ABSY: u8 = 0x9E;
+/// store accumulator, x and high byte + 1 zero page y indexed indirect
+pub const AHX_ZPYI: u8 = 0x93;
+/// store accumulator, x and high byte + 1 absolute y indexed
+pub const AHX_ABSY: u8 = 0x9F;
+
+/* 65C02 OPCODES */
+
+/// branch always
+pub const BRA: u8 = 0x80;
+/// push x index
+pub const PHX: u8 = 0xDA;
+/// push y index
+pub const PHY: u8 = 0x5A;
+/// pull x index
+pub const PLX: u8 = 0xFA;
+/// pull y index
+pub const PLY: u8 = 0x7A;
+
+/// store zero zero page
+pub const STZ_ZP: u8 = 0x64;
+/// store zero zero page, x index
+pub const STZ_ZPX: u8 = 0x74;
+/// store zero absolute
+pub const STZ_ABS: u8 = 0x9C;
+/// store zero absolute x indexed
+pub const STZ_ABSX: u8 = 0x9E;
+
+/// test and set bits zero page
+pub const TSB_ZP: u8 = 0x04;
+/// test and set bits absolute
+pub const TSB_ABS: u8 = 0x0C;
+/// test and reset bits zero page
+pub const TRB_ZP: u8 = 0x14;
+/// test and reset bits absolute
+pub const TRB_ABS: u8 = 0x1C;
+
+/// or accumulator zero page indirect
+pub const ORA_ZPI: u8 = 0x12;
+/// and accumulator zero page indirect
+pub const ANDA_ZPI: u8 = 0x32;
+/// exclusive or accumulator zero page indirect
+pub const EORA_ZPI: u8 = 0x52;
+/// add with carry zero page indirect
+pub const ADC_ZPI: u8 = 0x72;
+/// store accumulator zero page indirect
+pub const STA_ZPI: u8 = 0x92;
+/// load accumulator zero page indirect
+pub const LDA_ZPI: u8 = 0xB2;
+/// compare accumulator zero page indirect
+pub const CMP_ZPI: u8 = 0xD2;
+/// subtract with borrow zero page indirect
+pub const SBC_ZPI: u8 = 0xF2;
+
+/// bit test immediate
+pub const BIT_IM: u8 = 0x89;
+/// bit test zero page, x index
+pub const BIT_ZPX: u8 = 0x34;
+/// bit test absolute x indexed
+pub const BIT_ABSX: u8 = 0x3C;
+
+/// increment accumulator
+pub const INC_ACC: u8 = 0x1A;
+/// decrement accumulator
+pub const DEC_ACC: u8 = 0x3A;
+/// jump absolute x indexed indirect
+pub const JMP_ABSXI: u8 = 0x7C;
+
+/* ROCKWELL AND WDC BIT OPCODES */
+
+/// reset bit 0 zero page
+pub const RMB0: u8 = 0x07;
+/// reset bit 1 zero page
+pub const RMB1: u8 = 0x17;
+/// reset bit 2 zero page
+pub const RMB2: u8 = 0x27;
+/// reset bit 3 zero page
+pub const RMB3: u8 = 0x37;
+/// reset bit 4 zero page
+pub const RMB4: u8 = 0x47;
+/// reset bit 5 zero page
+pub const RMB5: u8 = 0x57;
+/// reset bit 6 zero page
+pub const RMB6: u8 = 0x67;
+/// reset bit 7 zero page
+pub const RMB7: u8 = 0x77;
+
+/// set bit 0 zero page
+pub const SMB0: u8 = 0x87;
+/// set bit 1 zero page
+pub const SMB1: u8 = 0x97;
+/// set bit 2 zero page
+pub const SMB2: u8 = 0xA7;
+/// set bit 3 zero page
+pub const SMB3: u8 = 0xB7;
+/// set bit 4 zero page
+pub const SMB4: u8 = 0xC7;
+/// set bit 5 zero page
+pub const SMB5: u8 = 0xD7;
+/// set bit 6 zero page
+pub const SMB6: u8 = 0xE7;
+/// set bit 7 zero page
+pub const SMB7: u8 = 0xF7;
+
+/// branch if bit 0 of zero page is reset
+pub const BBR0: u8 = 0x0F;
+/// branch if bit 1 of zero page is reset
+pub const BBR1: u8 = 0x1F;
+/// branch if bit 2 of zero page is reset
+pub const BBR2: u8 = 0x2F;
+/// branch if bit 3 of zero page is reset
+pub const BBR3: u8 = 0x3F;
+/// branch if bit 4 of zero page is reset
+pub const BBR4: u8 = 0x4F;
+/// branch if bit 5 of zero page is reset
+pub const BBR5: u8 = 0x5F;
+/// branch if bit 6 of zero page is reset
+pub const BBR6: u8 = 0x6F;
+/// branch if bit 7 of zero page is reset
+pub const BBR7: u8 = 0x7F;
+
+/// branch if bit 0 of zero page is set
+pub const BBS0: u8 = 0x8F;
+/// branch if bit 1 of zero page is set
+pub const BBS1: u8 = 0x9F;
+/// branch if bit 2 of zero page is set
+pub const BBS2: u8 = 0xAF;
+/// branch if bit 3 of zero page is set
+pub const BBS3: u8 = 0xBF;
+/// branch if bit 4 of zero page is set
+pub const BBS4: u8 = 0xCF;
+/// branch if bit 5 of zero page is set
+pub const BBS5: u8 = 0xDF;
+/// branch if bit 6 of zero page is set
+pub const BBS6: u8 = 0xEF;
+/// branch if bit 7 of zero page is set
+pub const BBS7: u8 = 0xFF;
+
+/* WDC OPCODES */
+
+/// wait for interrupt
+pub const WAI: u8 = 0xCB;
+/// stop the clock until reset
+pub const STP: u8 = 0xDB;
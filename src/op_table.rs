@@ -0,0 +1,485 @@
+/*
+    opcode metadata
+    one entry per opcode of the nmos 6502, documented or not, and tables
+    for the 65c02 parts derived from it.
+    cycle counts are the base cost, see `OpInfo::page_penalty` for
+    indexed reads, taken branches add their own cycles when executed
+    - https://www.masswerk.at/6502/6502_instruction_set.html
+    - https://www.nesdev.org/wiki/CPU_unofficial_opcodes
+    - http://www.6502.org/tutorials/65c02opcodes.html
+*/
+
+use core::fmt;
+
+/// how an instruction finds its operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddrMode {
+    /// no operand
+    Implied,
+    /// operates on the accumulator
+    Accumulator,
+    /// #$nn
+    Immediate,
+    /// $nn
+    ZeroPage,
+    /// $nn,x
+    ZeroPageX,
+    /// $nn,y
+    ZeroPageY,
+    /// $nnnn
+    Absolute,
+    /// $nnnn,x
+    AbsoluteX,
+    /// $nnnn,y
+    AbsoluteY,
+    /// ($nnnn), only used by JMP
+    Indirect,
+    /// ($nn,x)
+    IndirectX,
+    /// ($nn),y
+    IndirectY,
+    /// signed offset from the next instruction, used by branches
+    Relative,
+    /// ($nn), 65c02 only
+    ZeroPageIndirect,
+    /// ($nnnn,x), only used by the 65c02 JMP
+    AbsoluteIndirectX,
+    /// $nn,rel, a zero page operand then a branch offset, used by BBR and BBS
+    ZeroPageRelative,
+}
+
+impl AddrMode {
+    /// number of bytes taken by the opcode and its operand
+    pub const fn bytes(self) -> u8 {
+        match self {
+            AddrMode::Implied | AddrMode::Accumulator => 1,
+            AddrMode::Immediate
+            | AddrMode::ZeroPage
+            | AddrMode::ZeroPageX
+            | AddrMode::ZeroPageY
+            | AddrMode::IndirectX
+            | AddrMode::IndirectY
+            | AddrMode::Relative
+            | AddrMode::ZeroPageIndirect => 2,
+            AddrMode::Absolute
+            | AddrMode::AbsoluteX
+            | AddrMode::AbsoluteY
+            | AddrMode::Indirect
+            | AddrMode::AbsoluteIndirectX
+            | AddrMode::ZeroPageRelative => 3,
+        }
+    }
+}
+
+/// what an opcode is and what it costs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpInfo {
+    /// assembler mnemonic, e.g. "LDA"
+    pub mnemonic: &'static str,
+    /// addressing mode of the operand
+    pub mode: AddrMode,
+    /// length in bytes including the opcode
+    pub len: u8,
+    /// base cycle count
+    pub cycles: u8,
+    /// one extra cycle is taken when the indexed address crosses a page
+    pub page_penalty: bool,
+    /// false for the undocumented (illegal) nmos opcodes
+    /// and the reserved no-ops of the 65c02
+    pub documented: bool,
+}
+
+impl fmt::Display for OpInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mnemonic)?;
+        match self.mode {
+            AddrMode::Implied => Ok(()),
+            AddrMode::Accumulator => write!(f, " a"),
+            AddrMode::Immediate => write!(f, " #imm"),
+            AddrMode::ZeroPage => write!(f, " zp"),
+            AddrMode::ZeroPageX => write!(f, " zp,x"),
+            AddrMode::ZeroPageY => write!(f, " zp,y"),
+            AddrMode::Absolute => write!(f, " abs"),
+            AddrMode::AbsoluteX => write!(f, " abs,x"),
+            AddrMode::AbsoluteY => write!(f, " abs,y"),
+            AddrMode::Indirect => write!(f, " (abs)"),
+            AddrMode::IndirectX => write!(f, " (zp,x)"),
+            AddrMode::IndirectY => write!(f, " (zp),y"),
+            AddrMode::Relative => write!(f, " rel"),
+            AddrMode::ZeroPageIndirect => write!(f, " (zp)"),
+            AddrMode::AbsoluteIndirectX => write!(f, " (abs,x)"),
+            AddrMode::ZeroPageRelative => write!(f, " zp,rel"),
+        }
+    }
+}
+
+/// a documented opcode
+const fn op(mnemonic: &'static str, mode: AddrMode, cycles: u8, page_penalty: bool) -> OpInfo {
+    OpInfo {
+        mnemonic,
+        mode,
+        len: mode.bytes(),
+        cycles,
+        page_penalty,
+        documented: true,
+    }
+}
+
+/// an undocumented opcode
+const fn undoc(mnemonic: &'static str, mode: AddrMode, cycles: u8, page_penalty: bool) -> OpInfo {
+    OpInfo {
+        documented: false,
+        ..op(mnemonic, mode, cycles, page_penalty)
+    }
+}
+
+use AddrMode::*;
+
+/// metadata for every nmos 6502 opcode, indexed by opcode
+pub static OPCODES: [OpInfo; 256] = NMOS;
+
+/// metadata for every opcode of the original 65c02
+pub static CMOS_OPCODES: [OpInfo; 256] = cmos(false, false);
+
+/// metadata for every opcode of the rockwell r65c02, which adds the bit instructions
+pub static ROCKWELL_OPCODES: [OpInfo; 256] = cmos(true, false);
+
+/// metadata for every opcode of the wdc 65c02, which adds the bit instructions, WAI and STP
+pub static WDC_OPCODES: [OpInfo; 256] = cmos(true, true);
+
+/// build a 65c02 table from the nmos one
+/// every undocumented nmos opcode is either a new instruction or a reserved no-op
+const fn cmos(bit_ops: bool, wai_stp: bool) -> [OpInfo; 256] {
+    let mut table = NMOS;
+
+    let mut opcode = 0;
+    while opcode < 256 {
+        // RMB and BBR work on clear bits and fill rows 0-7, SMB and BBS rows 8-F
+        let clear = opcode < 0x80;
+        if !table[opcode].documented {
+            table[opcode] = match opcode & 0x0F {
+                0x02 => undoc("NOP", Immediate, 2, false),
+                0x03 | 0x0B => undoc("NOP", Implied, 1, false),
+                0x07 if bit_ops && clear => op("RMB", ZeroPage, 5, false),
+                0x07 if bit_ops => op("SMB", ZeroPage, 5, false),
+                0x0F if bit_ops && clear => op("BBR", ZeroPageRelative, 5, false),
+                0x0F if bit_ops => op("BBS", ZeroPageRelative, 5, false),
+                0x07 | 0x0F => undoc("NOP", Implied, 1, false),
+                _ => table[opcode],
+            };
+        }
+        opcode += 1;
+    }
+
+    // (zp) versions of the accumulator instructions
+    table[0x12] = op("ORA", ZeroPageIndirect, 5, false);
+    table[0x32] = op("AND", ZeroPageIndirect, 5, false);
+    table[0x52] = op("EOR", ZeroPageIndirect, 5, false);
+    table[0x72] = op("ADC", ZeroPageIndirect, 5, false);
+    table[0x92] = op("STA", ZeroPageIndirect, 5, false);
+    table[0xB2] = op("LDA", ZeroPageIndirect, 5, false);
+    table[0xD2] = op("CMP", ZeroPageIndirect, 5, false);
+    table[0xF2] = op("SBC", ZeroPageIndirect, 5, false);
+
+    table[0x04] = op("TSB", ZeroPage, 5, false);
+    table[0x0C] = op("TSB", Absolute, 6, false);
+    table[0x14] = op("TRB", ZeroPage, 5, false);
+    table[0x1C] = op("TRB", Absolute, 6, false);
+    table[0x1A] = op("INC", Accumulator, 2, false);
+    table[0x3A] = op("DEC", Accumulator, 2, false);
+    table[0x34] = op("BIT", ZeroPageX, 4, false);
+    table[0x3C] = op("BIT", AbsoluteX, 4, true);
+    table[0x89] = op("BIT", Immediate, 2, false);
+    table[0x5A] = op("PHY", Implied, 3, false);
+    table[0x7A] = op("PLY", Implied, 4, false);
+    table[0xDA] = op("PHX", Implied, 3, false);
+    table[0xFA] = op("PLX", Implied, 4, false);
+    table[0x64] = op("STZ", ZeroPage, 3, false);
+    table[0x74] = op("STZ", ZeroPageX, 4, false);
+    table[0x9C] = op("STZ", Absolute, 4, false);
+    table[0x9E] = op("STZ", AbsoluteX, 5, false);
+    table[0x80] = op("BRA", Relative, 2, false);
+    table[0x7C] = op("JMP", AbsoluteIndirectX, 6, false);
+
+    // JMP (abs) no longer wraps within the page, at the cost of a cycle
+    table[0x6C] = op("JMP", Indirect, 6, false);
+    // shifts and rotates only pay for the index fixup when a page is crossed
+    table[0x1E] = op("ASL", AbsoluteX, 6, true);
+    table[0x3E] = op("ROL", AbsoluteX, 6, true);
+    table[0x5E] = op("LSR", AbsoluteX, 6, true);
+    table[0x7E] = op("ROR", AbsoluteX, 6, true);
+
+    // the rest of the nmos multi-byte no-ops
+    table[0x44] = undoc("NOP", ZeroPage, 3, false);
+    table[0x54] = undoc("NOP", ZeroPageX, 4, false);
+    table[0xD4] = undoc("NOP", ZeroPageX, 4, false);
+    table[0xF4] = undoc("NOP", ZeroPageX, 4, false);
+    table[0x5C] = undoc("NOP", Absolute, 8, false);
+    table[0xDC] = undoc("NOP", Absolute, 4, false);
+    table[0xFC] = undoc("NOP", Absolute, 4, false);
+
+    if wai_stp {
+        table[0xCB] = op("WAI", Implied, 3, false);
+        table[0xDB] = op("STP", Implied, 3, false);
+    }
+    table
+}
+
+const NMOS: [OpInfo; 256] = [
+    /* 0x00 */ op("BRK", Implied, 7, false),
+    /* 0x01 */ op("ORA", IndirectX, 6, false),
+    /* 0x02 */ undoc("KIL", Implied, 2, false),
+    /* 0x03 */ undoc("SLO", IndirectX, 8, false),
+    /* 0x04 */ undoc("NOP", ZeroPage, 3, false),
+    /* 0x05 */ op("ORA", ZeroPage, 3, false),
+    /* 0x06 */ op("ASL", ZeroPage, 5, false),
+    /* 0x07 */ undoc("SLO", ZeroPage, 5, false),
+    /* 0x08 */ op("PHP", Implied, 3, false),
+    /* 0x09 */ op("ORA", Immediate, 2, false),
+    /* 0x0A */ op("ASL", Accumulator, 2, false),
+    /* 0x0B */ undoc("ANC", Immediate, 2, false),
+    /* 0x0C */ undoc("NOP", Absolute, 4, false),
+    /* 0x0D */ op("ORA", Absolute, 4, false),
+    /* 0x0E */ op("ASL", Absolute, 6, false),
+    /* 0x0F */ undoc("SLO", Absolute, 6, false),
+    /* 0x10 */ op("BPL", Relative, 2, false),
+    /* 0x11 */ op("ORA", IndirectY, 5, true),
+    /* 0x12 */ undoc("KIL", Implied, 2, false),
+    /* 0x13 */ undoc("SLO", IndirectY, 8, false),
+    /* 0x14 */ undoc("NOP", ZeroPageX, 4, false),
+    /* 0x15 */ op("ORA", ZeroPageX, 4, false),
+    /* 0x16 */ op("ASL", ZeroPageX, 6, false),
+    /* 0x17 */ undoc("SLO", ZeroPageX, 6, false),
+    /* 0x18 */ op("CLC", Implied, 2, false),
+    /* 0x19 */ op("ORA", AbsoluteY, 4, true),
+    /* 0x1A */ undoc("NOP", Implied, 2, false),
+    /* 0x1B */ undoc("SLO", AbsoluteY, 7, false),
+    /* 0x1C */ undoc("NOP", AbsoluteX, 4, true),
+    /* 0x1D */ op("ORA", AbsoluteX, 4, true),
+    /* 0x1E */ op("ASL", AbsoluteX, 7, false),
+    /* 0x1F */ undoc("SLO", AbsoluteX, 7, false),
+    /* 0x20 */ op("JSR", Absolute, 6, false),
+    /* 0x21 */ op("AND", IndirectX, 6, false),
+    /* 0x22 */ undoc("KIL", Implied, 2, false),
+    /* 0x23 */ undoc("RLA", IndirectX, 8, false),
+    /* 0x24 */ op("BIT", ZeroPage, 3, false),
+    /* 0x25 */ op("AND", ZeroPage, 3, false),
+    /* 0x26 */ op("ROL", ZeroPage, 5, false),
+    /* 0x27 */ undoc("RLA", ZeroPage, 5, false),
+    /* 0x28 */ op("PLP", Implied, 4, false),
+    /* 0x29 */ op("AND", Immediate, 2, false),
+    /* 0x2A */ op("ROL", Accumulator, 2, false),
+    /* 0x2B */ undoc("ANC", Immediate, 2, false),
+    /* 0x2C */ op("BIT", Absolute, 4, false),
+    /* 0x2D */ op("AND", Absolute, 4, false),
+    /* 0x2E */ op("ROL", Absolute, 6, false),
+    /* 0x2F */ undoc("RLA", Absolute, 6, false),
+    /* 0x30 */ op("BMI", Relative, 2, false),
+    /* 0x31 */ op("AND", IndirectY, 5, true),
+    /* 0x32 */ undoc("KIL", Implied, 2, false),
+    /* 0x33 */ undoc("RLA", IndirectY, 8, false),
+    /* 0x34 */ undoc("NOP", ZeroPageX, 4, false),
+    /* 0x35 */ op("AND", ZeroPageX, 4, false),
+    /* 0x36 */ op("ROL", ZeroPageX, 6, false),
+    /* 0x37 */ undoc("RLA", ZeroPageX, 6, false),
+    /* 0x38 */ op("SEC", Implied, 2, false),
+    /* 0x39 */ op("AND", AbsoluteY, 4, true),
+    /* 0x3A */ undoc("NOP", Implied, 2, false),
+    /* 0x3B */ undoc("RLA", AbsoluteY, 7, false),
+    /* 0x3C */ undoc("NOP", AbsoluteX, 4, true),
+    /* 0x3D */ op("AND", AbsoluteX, 4, true),
+    /* 0x3E */ op("ROL", AbsoluteX, 7, false),
+    /* 0x3F */ undoc("RLA", AbsoluteX, 7, false),
+    /* 0x40 */ op("RTI", Implied, 6, false),
+    /* 0x41 */ op("EOR", IndirectX, 6, false),
+    /* 0x42 */ undoc("KIL", Implied, 2, false),
+    /* 0x43 */ undoc("SRE", IndirectX, 8, false),
+    /* 0x44 */ undoc("NOP", ZeroPage, 3, false),
+    /* 0x45 */ op("EOR", ZeroPage, 3, false),
+    /* 0x46 */ op("LSR", ZeroPage, 5, false),
+    /* 0x47 */ undoc("SRE", ZeroPage, 5, false),
+    /* 0x48 */ op("PHA", Implied, 3, false),
+    /* 0x49 */ op("EOR", Immediate, 2, false),
+    /* 0x4A */ op("LSR", Accumulator, 2, false),
+    /* 0x4B */ undoc("ALR", Immediate, 2, false),
+    /* 0x4C */ op("JMP", Absolute, 3, false),
+    /* 0x4D */ op("EOR", Absolute, 4, false),
+    /* 0x4E */ op("LSR", Absolute, 6, false),
+    /* 0x4F */ undoc("SRE", Absolute, 6, false),
+    /* 0x50 */ op("BVC", Relative, 2, false),
+    /* 0x51 */ op("EOR", IndirectY, 5, true),
+    /* 0x52 */ undoc("KIL", Implied, 2, false),
+    /* 0x53 */ undoc("SRE", IndirectY, 8, false),
+    /* 0x54 */ undoc("NOP", ZeroPageX, 4, false),
+    /* 0x55 */ op("EOR", ZeroPageX, 4, false),
+    /* 0x56 */ op("LSR", ZeroPageX, 6, false),
+    /* 0x57 */ undoc("SRE", ZeroPageX, 6, false),
+    /* 0x58 */ op("CLI", Implied, 2, false),
+    /* 0x59 */ op("EOR", AbsoluteY, 4, true),
+    /* 0x5A */ undoc("NOP", Implied, 2, false),
+    /* 0x5B */ undoc("SRE", AbsoluteY, 7, false),
+    /* 0x5C */ undoc("NOP", AbsoluteX, 4, true),
+    /* 0x5D */ op("EOR", AbsoluteX, 4, true),
+    /* 0x5E */ op("LSR", AbsoluteX, 7, false),
+    /* 0x5F */ undoc("SRE", AbsoluteX, 7, false),
+    /* 0x60 */ op("RTS", Implied, 6, false),
+    /* 0x61 */ op("ADC", IndirectX, 6, false),
+    /* 0x62 */ undoc("KIL", Implied, 2, false),
+    /* 0x63 */ undoc("RRA", IndirectX, 8, false),
+    /* 0x64 */ undoc("NOP", ZeroPage, 3, false),
+    /* 0x65 */ op("ADC", ZeroPage, 3, false),
+    /* 0x66 */ op("ROR", ZeroPage, 5, false),
+    /* 0x67 */ undoc("RRA", ZeroPage, 5, false),
+    /* 0x68 */ op("PLA", Implied, 4, false),
+    /* 0x69 */ op("ADC", Immediate, 2, false),
+    /* 0x6A */ op("ROR", Accumulator, 2, false),
+    /* 0x6B */ undoc("ARR", Immediate, 2, false),
+    /* 0x6C */ op("JMP", Indirect, 5, false),
+    /* 0x6D */ op("ADC", Absolute, 4, false),
+    /* 0x6E */ op("ROR", Absolute, 6, false),
+    /* 0x6F */ undoc("RRA", Absolute, 6, false),
+    /* 0x70 */ op("BVS", Relative, 2, false),
+    /* 0x71 */ op("ADC", IndirectY, 5, true),
+    /* 0x72 */ undoc("KIL", Implied, 2, false),
+    /* 0x73 */ undoc("RRA", IndirectY, 8, false),
+    /* 0x74 */ undoc("NOP", ZeroPageX, 4, false),
+    /* 0x75 */ op("ADC", ZeroPageX, 4, false),
+    /* 0x76 */ op("ROR", ZeroPageX, 6, false),
+    /* 0x77 */ undoc("RRA", ZeroPageX, 6, false),
+    /* 0x78 */ op("SEI", Implied, 2, false),
+    /* 0x79 */ op("ADC", AbsoluteY, 4, true),
+    /* 0x7A */ undoc("NOP", Implied, 2, false),
+    /* 0x7B */ undoc("RRA", AbsoluteY, 7, false),
+    /* 0x7C */ undoc("NOP", AbsoluteX, 4, true),
+    /* 0x7D */ op("ADC", AbsoluteX, 4, true),
+    /* 0x7E */ op("ROR", AbsoluteX, 7, false),
+    /* 0x7F */ undoc("RRA", AbsoluteX, 7, false),
+    /* 0x80 */ undoc("NOP", Immediate, 2, false),
+    /* 0x81 */ op("STA", IndirectX, 6, false),
+    /* 0x82 */ undoc("NOP", Immediate, 2, false),
+    /* 0x83 */ undoc("SAX", IndirectX, 6, false),
+    /* 0x84 */ op("STY", ZeroPage, 3, false),
+    /* 0x85 */ op("STA", ZeroPage, 3, false),
+    /* 0x86 */ op("STX", ZeroPage, 3, false),
+    /* 0x87 */ undoc("SAX", ZeroPage, 3, false),
+    /* 0x88 */ op("DEY", Implied, 2, false),
+    /* 0x89 */ undoc("NOP", Immediate, 2, false),
+    /* 0x8A */ op("TXA", Implied, 2, false),
+    /* 0x8B */ undoc("XAA", Immediate, 2, false),
+    /* 0x8C */ op("STY", Absolute, 4, false),
+    /* 0x8D */ op("STA", Absolute, 4, false),
+    /* 0x8E */ op("STX", Absolute, 4, false),
+    /* 0x8F */ undoc("SAX", Absolute, 4, false),
+    /* 0x90 */ op("BCC", Relative, 2, false),
+    /* 0x91 */ op("STA", IndirectY, 6, false),
+    /* 0x92 */ undoc("KIL", Implied, 2, false),
+    /* 0x93 */ undoc("AHX", IndirectY, 6, false),
+    /* 0x94 */ op("STY", ZeroPageX, 4, false),
+    /* 0x95 */ op("STA", ZeroPageX, 4, false),
+    /* 0x96 */ op("STX", ZeroPageY, 4, false),
+    /* 0x97 */ undoc("SAX", ZeroPageY, 4, false),
+    /* 0x98 */ op("TYA", Implied, 2, false),
+    /* 0x99 */ op("STA", AbsoluteY, 5, false),
+    /* 0x9A */ op("TXS", Implied, 2, false),
+    /* 0x9B */ undoc("TAS", AbsoluteY, 5, false),
+    /* 0x9C */ undoc("SHY", AbsoluteX, 5, false),
+    /* 0x9D */ op("STA", AbsoluteX, 5, false),
+    /* 0x9E */ undoc("SHX", AbsoluteY, 5, false),
+    /* 0x9F */ undoc("AHX", AbsoluteY, 5, false),
+    /* 0xA0 */ op("LDY", Immediate, 2, false),
+    /* 0xA1 */ op("LDA", IndirectX, 6, false),
+    /* 0xA2 */ op("LDX", Immediate, 2, false),
+    /* 0xA3 */ undoc("LAX", IndirectX, 6, false),
+    /* 0xA4 */ op("LDY", ZeroPage, 3, false),
+    /* 0xA5 */ op("LDA", ZeroPage, 3, false),
+    /* 0xA6 */ op("LDX", ZeroPage, 3, false),
+    /* 0xA7 */ undoc("LAX", ZeroPage, 3, false),
+    /* 0xA8 */ op("TAY", Implied, 2, false),
+    /* 0xA9 */ op("LDA", Immediate, 2, false),
+    /* 0xAA */ op("TAX", Implied, 2, false),
+    /* 0xAB */ undoc("LAX", Immediate, 2, false),
+    /* 0xAC */ op("LDY", Absolute, 4, false),
+    /* 0xAD */ op("LDA", Absolute, 4, false),
+    /* 0xAE */ op("LDX", Absolute, 4, false),
+    /* 0xAF */ undoc("LAX", Absolute, 4, false),
+    /* 0xB0 */ op("BCS", Relative, 2, false),
+    /* 0xB1 */ op("LDA", IndirectY, 5, true),
+    /* 0xB2 */ undoc("KIL", Implied, 2, false),
+    /* 0xB3 */ undoc("LAX", IndirectY, 5, true),
+    /* 0xB4 */ op("LDY", ZeroPageX, 4, false),
+    /* 0xB5 */ op("LDA", ZeroPageX, 4, false),
+    /* 0xB6 */ op("LDX", ZeroPageY, 4, false),
+    /* 0xB7 */ undoc("LAX", ZeroPageY, 4, false),
+    /* 0xB8 */ op("CLV", Implied, 2, false),
+    /* 0xB9 */ op("LDA", AbsoluteY, 4, true),
+    /* 0xBA */ op("TSX", Implied, 2, false),
+    /* 0xBB */ undoc("LAS", AbsoluteY, 4, true),
+    /* 0xBC */ op("LDY", AbsoluteX, 4, true),
+    /* 0xBD */ op("LDA", AbsoluteX, 4, true),
+    /* 0xBE */ op("LDX", AbsoluteY, 4, true),
+    /* 0xBF */ undoc("LAX", AbsoluteY, 4, true),
+    /* 0xC0 */ op("CPY", Immediate, 2, false),
+    /* 0xC1 */ op("CMP", IndirectX, 6, false),
+    /* 0xC2 */ undoc("NOP", Immediate, 2, false),
+    /* 0xC3 */ undoc("DCP", IndirectX, 8, false),
+    /* 0xC4 */ op("CPY", ZeroPage, 3, false),
+    /* 0xC5 */ op("CMP", ZeroPage, 3, false),
+    /* 0xC6 */ op("DEC", ZeroPage, 5, false),
+    /* 0xC7 */ undoc("DCP", ZeroPage, 5, false),
+    /* 0xC8 */ op("INY", Implied, 2, false),
+    /* 0xC9 */ op("CMP", Immediate, 2, false),
+    /* 0xCA */ op("DEX", Implied, 2, false),
+    /* 0xCB */ undoc("AXS", Immediate, 2, false),
+    /* 0xCC */ op("CPY", Absolute, 4, false),
+    /* 0xCD */ op("CMP", Absolute, 4, false),
+    /* 0xCE */ op("DEC", Absolute, 6, false),
+    /* 0xCF */ undoc("DCP", Absolute, 6, false),
+    /* 0xD0 */ op("BNE", Relative, 2, false),
+    /* 0xD1 */ op("CMP", IndirectY, 5, true),
+    /* 0xD2 */ undoc("KIL", Implied, 2, false),
+    /* 0xD3 */ undoc("DCP", IndirectY, 8, false),
+    /* 0xD4 */ undoc("NOP", ZeroPageX, 4, false),
+    /* 0xD5 */ op("CMP", ZeroPageX, 4, false),
+    /* 0xD6 */ op("DEC", ZeroPageX, 6, false),
+    /* 0xD7 */ undoc("DCP", ZeroPageX, 6, false),
+    /* 0xD8 */ op("CLD", Implied, 2, false),
+    /* 0xD9 */ op("CMP", AbsoluteY, 4, true),
+    /* 0xDA */ undoc("NOP", Implied, 2, false),
+    /* 0xDB */ undoc("DCP", AbsoluteY, 7, false),
+    /* 0xDC */ undoc("NOP", AbsoluteX, 4, true),
+    /* 0xDD */ op("CMP", AbsoluteX, 4, true),
+    /* 0xDE */ op("DEC", AbsoluteX, 7, false),
+    /* 0xDF */ undoc("DCP", AbsoluteX, 7, false),
+    /* 0xE0 */ op("CPX", Immediate, 2, false),
+    /* 0xE1 */ op("SBC", IndirectX, 6, false),
+    /* 0xE2 */ undoc("NOP", Immediate, 2, false),
+    /* 0xE3 */ undoc("ISC", IndirectX, 8, false),
+    /* 0xE4 */ op("CPX", ZeroPage, 3, false),
+    /* 0xE5 */ op("SBC", ZeroPage, 3, false),
+    /* 0xE6 */ op("INC", ZeroPage, 5, false),
+    /* 0xE7 */ undoc("ISC", ZeroPage, 5, false),
+    /* 0xE8 */ op("INX", Implied, 2, false),
+    /* 0xE9 */ op("SBC", Immediate, 2, false),
+    /* 0xEA */ op("NOP", Implied, 2, false),
+    /* 0xEB */ undoc("SBC", Immediate, 2, false),
+    /* 0xEC */ op("CPX", Absolute, 4, false),
+    /* 0xED */ op("SBC", Absolute, 4, false),
+    /* 0xEE */ op("INC", Absolute, 6, false),
+    /* 0xEF */ undoc("ISC", Absolute, 6, false),
+    /* 0xF0 */ op("BEQ", Relative, 2, false),
+    /* 0xF1 */ op("SBC", IndirectY, 5, true),
+    /* 0xF2 */ undoc("KIL", Implied, 2, false),
+    /* 0xF3 */ undoc("ISC", IndirectY, 8, false),
+    /* 0xF4 */ undoc("NOP", ZeroPageX, 4, false),
+    /* 0xF5 */ op("SBC", ZeroPageX, 4, false),
+    /* 0xF6 */ op("INC", ZeroPageX, 6, false),
+    /* 0xF7 */ undoc("ISC", ZeroPageX, 6, false),
+    /* 0xF8 */ op("SED", Implied, 2, false),
+    /* 0xF9 */ op("SBC", AbsoluteY, 4, true),
+    /* 0xFA */ undoc("NOP", Implied, 2, false),
+    /* 0xFB */ undoc("ISC", AbsoluteY, 7, false),
+    /* 0xFC */ undoc("NOP", AbsoluteX, 4, true),
+    /* 0xFD */ op("SBC", AbsoluteX, 4, true),
+    /* 0xFE */ op("INC", AbsoluteX, 7, false),
+    /* 0xFF */ undoc("ISC", AbsoluteX, 7, false),
+];
@@ -15,6 +15,7 @@ bitflags! {
     pub struct ProcStat: u8 {
         const N = 0b1000_0000; // negative
         const V = 0b0100_0000; // overflow
+        const U = 0b0010_0000; // unused, always pushed as 1
         const B = 0b0001_0000; // break
         const D = 0b0000_1000; // decimal
         const I = 0b0000_0100; // interrupt disable
@@ -28,6 +29,19 @@ impl ProcStat {
         self.bits = 0;
         self
     }
+
+    /// the value written to the stack, B only exists here and
+    /// tells BRK and PHP (set) apart from IRQ and NMI (clear)
+    pub fn to_stack(self, brk: bool) -> u8 {
+        let mut pushed = self | ProcStat::U;
+        pushed.set(ProcStat::B, brk);
+        pushed.bits
+    }
+
+    /// the value restored from the stack, B and the unused bit are dropped
+    pub fn from_stack(data: u8) -> Self {
+        ProcStat::from_bits_truncate(data) - (ProcStat::B | ProcStat::U)
+    }
 }
 
 impl fmt::Display for ProcStat {
@@ -0,0 +1,71 @@
+/*
+    cpu variants
+    members of the 6502 family share the nmos instruction set but differ in decode,
+    timing and a handful of behaviours. the cmos parts replace every undocumented
+    nmos opcode with a new instruction or a no-op, fix JMP (ind) and give valid
+    flags in decimal mode
+    - http://www.6502.org/tutorials/65c02opcodes.html
+    - https://www.nesdev.org/wiki/CPU
+*/
+
+use crate::op_table::{OpInfo, CMOS_OPCODES, OPCODES, ROCKWELL_OPCODES, WDC_OPCODES};
+
+/// which member of the 6502 family a `Cpu` emulates
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// the original nmos 6502, with its undocumented opcodes and JMP (ind) bug
+    #[default]
+    Nmos6502,
+    /// the ricoh 2a03 of the nes, an nmos 6502 without decimal mode,
+    /// D can still be set and cleared but ADC and SBC ignore it
+    Ricoh2A03,
+    /// the mos 6510 of the commodore 64, an nmos 6502 with an i/o port
+    /// at 0x0000 and 0x0001
+    Mos6510,
+    /// the original cmos 65c02
+    Cmos65C02,
+    /// the rockwell r65c02, which adds RMB, SMB, BBR and BBS
+    Rockwell65C02,
+    /// the wdc w65c02s, which adds the rockwell instructions, WAI and STP
+    Wdc65C02,
+}
+
+impl Variant {
+    /// metadata for every opcode as this variant decodes it
+    pub fn opcodes(self) -> &'static [OpInfo; 256] {
+        match self {
+            Variant::Nmos6502 | Variant::Ricoh2A03 | Variant::Mos6510 => &OPCODES,
+            Variant::Cmos65C02 => &CMOS_OPCODES,
+            Variant::Rockwell65C02 => &ROCKWELL_OPCODES,
+            Variant::Wdc65C02 => &WDC_OPCODES,
+        }
+    }
+
+    /// true for the 65c02 parts
+    pub fn is_cmos(self) -> bool {
+        matches!(
+            self,
+            Variant::Cmos65C02 | Variant::Rockwell65C02 | Variant::Wdc65C02
+        )
+    }
+
+    /// false if ADC and SBC ignore the D flag
+    pub fn has_decimal(self) -> bool {
+        self != Variant::Ricoh2A03
+    }
+
+    /// true if RMB, SMB, BBR and BBS are decoded
+    pub fn has_bit_ops(self) -> bool {
+        matches!(self, Variant::Rockwell65C02 | Variant::Wdc65C02)
+    }
+
+    /// true if the WAI and STP low power instructions are decoded
+    pub fn has_wai_stp(self) -> bool {
+        self == Variant::Wdc65C02
+    }
+
+    /// true if 0x0000 and 0x0001 are the on-chip i/o port instead of memory
+    pub fn has_io_port(self) -> bool {
+        self == Variant::Mos6510
+    }
+}
@@ -0,0 +1,38 @@
+//! fixtures shared by the regression suites
+#![allow(dead_code)]
+
+use q_6502::{cpu::Cpu, error::CpuError, mem::Memory, variant::Variant};
+
+/// where every test program is loaded and started
+pub const START: u16 = 0x0200;
+
+/// an nmos cpu about to run program at START
+pub fn cpu(program: &[u8]) -> Cpu {
+    cpu_on(Variant::Nmos6502, program)
+}
+
+/// a cpu of variant about to run program at START
+pub fn cpu_on(variant: Variant, program: &[u8]) -> Cpu {
+    let mut cpu = Cpu::with_variant(variant, Memory::default());
+    cpu.reset_to(START);
+    cpu.load_program(START, program).unwrap();
+    cpu
+}
+
+/// run program at START on an nmos cpu after setup, until the pc reaches its end
+pub fn run(program: &[u8], setup: impl FnOnce(&mut Cpu)) -> Cpu {
+    run_on(Variant::Nmos6502, program, setup).unwrap()
+}
+
+/// run program at START on variant after setup, until the pc reaches its end
+pub fn run_on(
+    variant: Variant,
+    program: &[u8],
+    setup: impl FnOnce(&mut Cpu),
+) -> Result<Cpu, CpuError> {
+    let mut cpu = cpu_on(variant, program);
+    setup(&mut cpu);
+    let end = START + program.len() as u16;
+    cpu.run_until(|cpu| cpu.pc == end)?;
+    Ok(cpu)
+}
@@ -0,0 +1,52 @@
+//! regression suite for cycle counting
+//! the base cost comes from the opcode table, indexed reads that cross a page
+//! and taken branches add their own cycles, stores and read-modify-write never do
+
+mod common;
+
+use q_6502::op_codes::*;
+
+/// name, program, value of x and y, cycles taken
+/// the pointer at 0x10 points to 0x12FF so (zp),y crosses a page when y is not 0
+const CASES: &[(&str, &[u8], u8, u8)] = &[
+    ("lda imm", &[LDA_IM, 0x00], 0, 2),
+    ("lda abs,x", &[LDA_ABSX, 0x00, 0x12], 1, 4),
+    ("lda abs,x page cross", &[LDA_ABSX, 0xFF, 0x12], 1, 5),
+    ("lda abs,y page cross", &[LDA_ABSY, 0xFF, 0x12], 1, 5),
+    ("lda (zp),y", &[LDA_ZPYI, 0x10], 0, 5),
+    ("lda (zp),y page cross", &[LDA_ZPYI, 0x10], 1, 6),
+    ("sta abs", &[STA_ABS, 0x00, 0x12], 0, 4),
+    ("sta abs,x", &[STA_ABSX, 0x00, 0x12], 1, 5),
+    ("sta abs,x page cross", &[STA_ABSX, 0xFF, 0x12], 1, 5),
+    ("sta (zp),y", &[STA_ZPYI, 0x10], 0, 6),
+    ("sta (zp),y page cross", &[STA_ZPYI, 0x10], 1, 6),
+    ("asl a", &[ASL_ACC], 0, 2),
+    ("asl zp", &[ASL_ZP, 0x20], 0, 5),
+    ("inc zp", &[INC_ZP, 0x20], 0, 5),
+    ("inc abs,x", &[INC_ABSX, 0x00, 0x12], 1, 7),
+    ("inc abs,x page cross", &[INC_ABSX, 0xFF, 0x12], 1, 7),
+    ("beq not taken", &[BEQ, 0x10], 0, 2),
+    ("bne taken", &[BNE, 0x10], 0, 3),
+    ("bne taken page cross", &[BNE, 0xF0], 0, 4),
+    ("jmp abs", &[JMP_ABS, 0x00, 0x12], 0, 3),
+    ("jmp ind", &[JMP_IND, 0x10, 0x00], 0, 5),
+    ("jsr", &[JSR, 0x00, 0x12], 0, 6),
+    ("pha", &[PHA], 0, 3),
+    ("pla", &[PLA], 0, 4),
+    ("brk", &[BRK, 0x00], 0, 7),
+];
+
+#[test]
+fn instructions_take_their_documented_cycles() {
+    for &(name, program, index, cycles) in CASES {
+        let mut cpu = common::cpu(program);
+        cpu.x = index;
+        cpu.y = index;
+        cpu.mem.data[0x10] = 0xFF;
+        cpu.mem.data[0x11] = 0x12;
+
+        let before = cpu.cycles;
+        assert_eq!(cpu.step().unwrap().cycles(), cycles, "{name}");
+        assert_eq!(cpu.cycles - before, cycles as u64, "{name}");
+    }
+}
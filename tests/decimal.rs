@@ -0,0 +1,117 @@
+//! regression suite for decimal mode ADC and SBC
+//! the nmos 6502 takes N, V and Z from intermediate results, the 65c02 gives
+//! valid N and Z at the cost of a cycle and the 2a03 ignores D entirely
+
+mod common;
+
+use common::{cpu_on, run, run_on};
+use q_6502::{
+    cpu::{Cpu, Step},
+    op_codes::*,
+    proc_stat::ProcStat,
+    variant::Variant,
+};
+
+/// opcode #value on a = a with D set and the given carry
+fn decimal(opcode: u8, carry: bool, a: u8, value: u8) -> Cpu {
+    decimal_on(Variant::Nmos6502, opcode, carry, a, value)
+}
+
+/// the same on variant
+fn decimal_on(variant: Variant, opcode: u8, carry: bool, a: u8, value: u8) -> Cpu {
+    run_on(variant, &[opcode, value], |cpu| {
+        cpu.a = a;
+        cpu.p.insert(ProcStat::D);
+        cpu.p.set(ProcStat::C, carry);
+    })
+    .unwrap()
+}
+
+#[test]
+fn adc_wraps_99_to_00_with_intermediate_flags() {
+    let cpu = decimal(ADC_IM, false, 0x99, 0x01);
+    assert_eq!(cpu.a, 0x00);
+    assert!(cpu.p.contains(ProcStat::C));
+    assert!(cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::Z));
+    assert!(!cpu.p.contains(ProcStat::V));
+}
+
+#[test]
+fn adc_adds_the_carry_in_bcd() {
+    let cpu = decimal(ADC_IM, true, 0x58, 0x46);
+    assert_eq!(cpu.a, 0x05);
+    assert!(cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn adc_takes_z_from_the_binary_sum() {
+    // 0x50 + 0x50 is 0x00 in bcd but 0xA0 in binary
+    let cpu = decimal(ADC_IM, false, 0x50, 0x50);
+    assert_eq!(cpu.a, 0x00);
+    assert!(cpu.p.contains(ProcStat::C));
+    assert!(!cpu.p.contains(ProcStat::Z));
+    assert!(cpu.p.contains(ProcStat::N));
+    assert!(cpu.p.contains(ProcStat::V));
+}
+
+#[test]
+fn sbc_borrows_00_to_99_with_binary_flags() {
+    let cpu = decimal(SBC_IM, true, 0x00, 0x01);
+    assert_eq!(cpu.a, 0x99);
+    assert!(!cpu.p.contains(ProcStat::C));
+    assert!(cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::Z));
+}
+
+#[test]
+fn binary_mode_ignores_bcd() {
+    let cpu = run(&[ADC_IM, 0x01], |cpu| cpu.a = 0x09);
+    assert_eq!(cpu.a, 0x0A);
+}
+
+#[test]
+fn cmos_adc_wraps_99_to_00_with_valid_flags() {
+    let cpu = decimal_on(Variant::Cmos65C02, ADC_IM, false, 0x99, 0x01);
+    assert_eq!(cpu.a, 0x00);
+    assert!(cpu.p.contains(ProcStat::C));
+    assert!(!cpu.p.contains(ProcStat::N));
+    assert!(cpu.p.contains(ProcStat::Z));
+
+    let cpu = decimal_on(Variant::Cmos65C02, ADC_IM, true, 0x58, 0x46);
+    assert_eq!(cpu.a, 0x05);
+    assert!(cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn cmos_sbc_borrows_00_to_99_with_valid_flags() {
+    let cpu = decimal_on(Variant::Cmos65C02, SBC_IM, true, 0x00, 0x01);
+    assert_eq!(cpu.a, 0x99);
+    assert!(!cpu.p.contains(ProcStat::C));
+    assert!(cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::Z));
+}
+
+#[test]
+fn cmos_decimal_adc_and_sbc_take_an_extra_cycle() {
+    for (variant, cycles) in [(Variant::Nmos6502, 2), (Variant::Cmos65C02, 3)] {
+        for opcode in [ADC_IM, SBC_IM] {
+            let mut cpu = cpu_on(variant, &[SED, opcode, 0x01]);
+            cpu.step().unwrap();
+
+            let step = cpu.step().unwrap();
+            assert!(matches!(step, Step::Instruction { .. }));
+            assert_eq!(step.cycles(), cycles, "{variant:?} 0x{opcode:02X}");
+        }
+    }
+}
+
+#[test]
+fn ricoh_ignores_the_decimal_flag() {
+    let cpu = decimal_on(Variant::Ricoh2A03, ADC_IM, false, 0x09, 0x01);
+    assert_eq!(cpu.a, 0x0A);
+    assert!(cpu.p.contains(ProcStat::D));
+
+    let cpu = decimal_on(Variant::Ricoh2A03, SBC_IM, true, 0x10, 0x01);
+    assert_eq!(cpu.a, 0x0F);
+}
@@ -0,0 +1,160 @@
+//! regression suite for (zp,x) and (zp),y operand resolution
+//! every instruction with these modes must agree with the hardware
+
+mod common;
+
+use common::run;
+use q_6502::{cpu::Cpu, op_codes::*, proc_stat::ProcStat};
+
+/// run opcode with operand 0x20 and x = 4, the pointer at 0x24 points to value
+/// 0x20 itself holds a decoy so reading the pointer location is caught
+fn zpxi(opcode: u8, a: u8, value: u8) -> Cpu {
+    run(&[opcode, 0x20], |cpu| {
+        cpu.a = a;
+        cpu.x = 0x04;
+        cpu.mem.data[0x20] = 0xEE;
+        cpu.mem.data[0x24] = 0x34;
+        cpu.mem.data[0x25] = 0x12;
+        cpu.mem.data[0x1234] = value;
+    })
+}
+
+/// run opcode with operand 0x40 and y = 0x10, the pointer at 0x40 plus y points to value
+/// a decoy pointer at 0x50 catches y being added to the operand
+fn zpyi(opcode: u8, a: u8, value: u8) -> Cpu {
+    run(&[opcode, 0x40], |cpu| {
+        cpu.a = a;
+        cpu.y = 0x10;
+        cpu.mem.data[0x40] = 0xF8;
+        cpu.mem.data[0x41] = 0x12;
+        cpu.mem.data[0x50] = 0x00;
+        cpu.mem.data[0x51] = 0x20;
+        cpu.mem.data[0x2000] = 0xEE;
+        cpu.mem.data[0x2010] = 0xEE;
+        cpu.mem.data[0x1308] = value;
+    })
+}
+
+#[test]
+fn lda_zpxi_dereferences_pointer() {
+    let cpu = zpxi(LDA_ZPXI, 0x00, 0x81);
+    assert_eq!(cpu.a, 0x81);
+    assert!(cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::Z));
+}
+
+#[test]
+fn lda_zpyi_adds_y_once() {
+    let cpu = zpyi(LDA_ZPYI, 0xFF, 0x00);
+    assert_eq!(cpu.a, 0x00);
+    assert!(cpu.p.contains(ProcStat::Z));
+}
+
+#[test]
+fn ora_indirect() {
+    assert_eq!(zpxi(ORA_ZPXI, 0x0F, 0x30).a, 0x3F);
+    assert_eq!(zpyi(ORA_ZPYI, 0x0F, 0x30).a, 0x3F);
+}
+
+#[test]
+fn and_indirect() {
+    assert_eq!(zpxi(ANDA_ZPXI, 0x3C, 0x0F).a, 0x0C);
+    assert_eq!(zpyi(ANDA_ZPYI, 0x3C, 0x0F).a, 0x0C);
+}
+
+#[test]
+fn eor_indirect() {
+    assert_eq!(zpxi(EORA_ZPXI, 0xFF, 0x0F).a, 0xF0);
+    assert_eq!(zpyi(EORA_ZPYI, 0xFF, 0x0F).a, 0xF0);
+}
+
+#[test]
+fn adc_indirect() {
+    assert_eq!(zpxi(ADC_ZPXI, 0x10, 0x22).a, 0x32);
+    assert_eq!(zpyi(ADC_ZPYI, 0x10, 0x22).a, 0x32);
+}
+
+#[test]
+fn sbc_indirect() {
+    let cpu = run(&[SEC, SBC_ZPXI, 0x20], |cpu| {
+        cpu.a = 0x50;
+        cpu.x = 0x04;
+        cpu.mem.data[0x24] = 0x34;
+        cpu.mem.data[0x25] = 0x12;
+        cpu.mem.data[0x1234] = 0x10;
+    });
+    assert_eq!(cpu.a, 0x40);
+    assert!(cpu.p.contains(ProcStat::C));
+
+    let cpu = run(&[SEC, SBC_ZPYI, 0x40], |cpu| {
+        cpu.a = 0x50;
+        cpu.y = 0x10;
+        cpu.mem.data[0x40] = 0xF8;
+        cpu.mem.data[0x41] = 0x12;
+        cpu.mem.data[0x1308] = 0x10;
+    });
+    assert_eq!(cpu.a, 0x40);
+}
+
+#[test]
+fn cmp_indirect() {
+    let cpu = zpxi(CMP_ZPXI, 0x40, 0x40);
+    assert!(cpu.p.contains(ProcStat::Z | ProcStat::C));
+
+    let cpu = zpyi(CMP_ZPYI, 0x40, 0x41);
+    assert!(!cpu.p.contains(ProcStat::Z));
+    assert!(!cpu.p.contains(ProcStat::C));
+    assert!(cpu.p.contains(ProcStat::N));
+}
+
+#[test]
+fn sta_indirect() {
+    assert_eq!(zpxi(STA_ZPXI, 0x5A, 0x00).mem.data[0x1234], 0x5A);
+    assert_eq!(zpyi(STA_ZPYI, 0x5A, 0x00).mem.data[0x1308], 0x5A);
+}
+
+#[test]
+fn zpxi_index_wraps_within_zero_page() {
+    let cpu = run(&[LDA_ZPXI, 0xF0], |cpu| {
+        cpu.x = 0x20;
+        cpu.mem.data[0x10] = 0x00;
+        cpu.mem.data[0x11] = 0x30;
+        cpu.mem.data[0x3000] = 0x42;
+    });
+    assert_eq!(cpu.a, 0x42);
+}
+
+#[test]
+fn zpxi_pointer_wraps_within_zero_page() {
+    let cpu = run(&[LDA_ZPXI, 0xFE], |cpu| {
+        cpu.x = 0x01;
+        cpu.mem.data[0xFF] = 0x00;
+        cpu.mem.data[0x00] = 0x30;
+        cpu.mem.data[0x0100] = 0x31;
+        cpu.mem.data[0x3000] = 0x42;
+    });
+    assert_eq!(cpu.a, 0x42);
+}
+
+#[test]
+fn zpyi_pointer_wraps_within_zero_page() {
+    let cpu = run(&[LDA_ZPYI, 0xFF], |cpu| {
+        cpu.y = 0x01;
+        cpu.mem.data[0xFF] = 0x00;
+        cpu.mem.data[0x00] = 0x30;
+        cpu.mem.data[0x0100] = 0x31;
+        cpu.mem.data[0x3001] = 0x42;
+    });
+    assert_eq!(cpu.a, 0x42);
+}
+
+#[test]
+fn zpyi_address_wraps_at_end_of_memory() {
+    let cpu = run(&[LDA_ZPYI, 0x40], |cpu| {
+        cpu.y = 0x10;
+        cpu.mem.data[0x40] = 0xF8;
+        cpu.mem.data[0x41] = 0xFF;
+        cpu.mem.data[0x0008] = 0x42;
+    });
+    assert_eq!(cpu.a, 0x42);
+}
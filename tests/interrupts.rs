@@ -0,0 +1,122 @@
+//! regression suite for the irq and nmi inputs
+//! both are serviced between instructions, irq for as long as it is held
+//! and I is clear, nmi once per falling edge
+
+mod common;
+
+use common::START;
+use q_6502::{
+    cpu::{Cpu, IRQ_VECTOR, NMI_VECTOR},
+    op_codes::*,
+    proc_stat::ProcStat,
+};
+
+/// counts irqs in x
+const IRQ_HANDLER: u16 = 0x0300;
+/// counts nmis in y
+const NMI_HANDLER: u16 = 0x0400;
+
+/// a cpu about to run program with I clear and both handlers installed
+fn cpu(program: &[u8]) -> Cpu {
+    let mut cpu = common::cpu(program);
+    cpu.p.remove(ProcStat::I);
+    cpu.sp = 0xFF;
+    cpu.mem.write_word(IRQ_VECTOR as usize, IRQ_HANDLER);
+    cpu.mem.write_word(NMI_VECTOR as usize, NMI_HANDLER);
+
+    let irq = IRQ_HANDLER as usize;
+    cpu.mem.data[irq..irq + 2].copy_from_slice(&[INX, NOP]);
+    let nmi = NMI_HANDLER as usize;
+    cpu.mem.data[nmi..nmi + 2].copy_from_slice(&[INY, NOP]);
+    cpu
+}
+
+#[test]
+fn irq_is_serviced_while_i_is_clear() {
+    let mut cpu = cpu(&[NOP]);
+    cpu.assert_irq();
+    cpu.step().unwrap();
+
+    assert_eq!(cpu.pc, IRQ_HANDLER);
+    assert!(cpu.p.contains(ProcStat::I));
+    assert_eq!(cpu.mem.data[0x01FF], (START >> 8) as u8);
+    assert_eq!(cpu.mem.data[0x01FE], START as u8);
+
+    // B only exists on the stack, and is clear for an interrupt
+    let pushed = cpu.mem.data[0x01FD];
+    assert_eq!(pushed & ProcStat::B.bits(), 0);
+    assert_ne!(pushed & ProcStat::U.bits(), 0);
+}
+
+#[test]
+fn irq_is_ignored_while_i_is_set() {
+    let mut cpu = cpu(&[NOP]);
+    cpu.p.insert(ProcStat::I);
+    cpu.assert_irq();
+    cpu.step().unwrap();
+
+    assert_eq!(cpu.pc, START + 1);
+}
+
+#[test]
+fn irq_is_level_triggered() {
+    let mut cpu = cpu(&[NOP]);
+    cpu.assert_irq();
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    assert_eq!(cpu.x, 1);
+
+    // still held, so it is taken again as soon as I is cleared
+    cpu.p.remove(ProcStat::I);
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, IRQ_HANDLER);
+
+    cpu.release_irq();
+    cpu.p.remove(ProcStat::I);
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, IRQ_HANDLER + 1);
+    assert_eq!(cpu.x, 2);
+}
+
+#[test]
+fn cli_unmasks_irq_one_instruction_late() {
+    // the poll before the NOP still sees I set, so the NOP runs first
+    let mut cpu = cpu(&[CLI, NOP]);
+    cpu.p.insert(ProcStat::I);
+    cpu.assert_irq();
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, START + 2);
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, IRQ_HANDLER);
+}
+
+#[test]
+fn sei_masks_irq_one_instruction_late() {
+    let mut cpu = cpu(&[SEI, NOP]);
+    cpu.step().unwrap();
+    cpu.assert_irq();
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, IRQ_HANDLER);
+}
+
+#[test]
+fn nmi_fires_once_per_edge() {
+    let mut cpu = cpu(&[NOP]);
+    cpu.p.insert(ProcStat::I);
+    cpu.assert_nmi();
+    cpu.assert_nmi();
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, NMI_HANDLER);
+
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    assert_eq!(cpu.y, 1);
+    assert_eq!(cpu.pc, NMI_HANDLER + 2);
+
+    cpu.release_nmi();
+    cpu.assert_nmi();
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, NMI_HANDLER);
+}
@@ -0,0 +1,136 @@
+//! regression suite for the 6510 i/o port at 0x0000 and 0x0001
+//! the port lives inside the cpu, so the bus never sees its accesses
+
+mod common;
+
+use common::START;
+use q_6502::{bus::Bus, cpu::Cpu, mem::Memory, op_codes::*, variant::Variant};
+
+/// ram that remembers every access to 0x0000 and 0x0001 and every port change
+#[derive(Default)]
+struct Watch {
+    mem: Memory,
+    touched: Vec<u16>,
+    changes: Vec<u8>,
+}
+
+impl Bus for Watch {
+    fn read(&mut self, address: u16) -> u8 {
+        if address <= 0x0001 {
+            self.touched.push(address);
+        }
+        self.mem.read(address)
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        if address <= 0x0001 {
+            self.touched.push(address);
+        }
+        self.mem.write(address, data)
+    }
+
+    fn peek(&self, address: u16) -> Option<u8> {
+        self.mem.peek(address)
+    }
+}
+
+fn record(bus: &mut Watch, pins: u8) {
+    bus.changes.push(pins);
+}
+
+/// a cpu of variant about to run program at START, with the port hook installed
+fn cpu(variant: Variant, program: &[u8]) -> Cpu<Watch> {
+    let mut cpu = Cpu::with_variant(variant, Watch::default());
+    cpu.on_port_change = Some(record);
+    cpu.reset_to(START);
+    cpu.load_program(START, program).unwrap();
+    cpu
+}
+
+/// run program at START on variant until the pc reaches its end
+fn run(variant: Variant, program: &[u8]) -> Cpu<Watch> {
+    let mut cpu = cpu(variant, program);
+    let end = START + program.len() as u16;
+    cpu.run_until(|cpu| cpu.pc == end).unwrap();
+    cpu
+}
+
+#[test]
+fn port_accesses_never_reach_the_bus() {
+    let cpu = run(
+        Variant::Mos6510,
+        &[LDA_IM, 0x2F, STA_ZP, 0x00, LDA_IM, 0x37, STA_ZP, 0x01, LDA_ZP, 0x00, LDX_ZP, 0x01],
+    );
+    assert_eq!(cpu.a, 0x2F);
+    // outputs from the latch, inputs pulled up
+    assert_eq!(cpu.x, 0xF7);
+    assert_eq!(cpu.port().direction, 0x2F);
+    assert_eq!(cpu.port().data, 0x37);
+
+    assert!(cpu.mem.touched.is_empty());
+    assert_eq!(cpu.mem.mem.data[0x0000..0x0002], [0x00, 0x00]);
+}
+
+#[test]
+fn on_port_change_fires_once_per_pin_change_and_on_reset() {
+    let mut cpu = run(
+        Variant::Mos6510,
+        &[
+            LDA_IM, 0x07, STA_ZP, 0x00, // pins 0-2 become outputs, driven low
+            STA_ZP, 0x01, // driven high again
+            STA_ZP, 0x01, // no change
+            STA_ABS, 0x00, 0x00, // no change
+        ],
+    );
+    assert_eq!(cpu.mem.changes, [0xFF, 0xF8, 0xFF]);
+
+    cpu.reset();
+    assert_eq!(cpu.mem.changes, [0xFF, 0xF8, 0xFF, 0xFF]);
+    assert_eq!(cpu.port().direction, 0x00);
+}
+
+#[test]
+fn tick_does_not_replay_port_changes() {
+    let mut cpu = cpu(Variant::Mos6510, &[INC_ZP, 0x00, NOP]);
+    cpu.mem.changes.clear();
+
+    // the five cycles of INC, then the NOP
+    for _ in 0..5 {
+        cpu.tick().unwrap();
+    }
+    assert_eq!(cpu.pc, START + 2);
+    assert_eq!(cpu.port().direction, 0x01);
+    assert_eq!(cpu.mem.changes, [0xFE]);
+
+    cpu.tick().unwrap();
+    cpu.tick().unwrap();
+    assert_eq!(cpu.mem.changes, [0xFE]);
+    assert!(cpu.mem.touched.is_empty());
+}
+
+#[test]
+fn set_port_input_only_drives_input_pins() {
+    let mut cpu = run(Variant::Mos6510, &[LDA_IM, 0x07, STA_ZP, 0x00, LDA_IM, 0x05, STA_ZP, 0x01]);
+    assert_eq!(cpu.port().pins(), 0xFD);
+    cpu.mem.changes.clear();
+
+    cpu.set_port_input(0x00);
+    assert_eq!(cpu.port().pins(), 0x05);
+    assert_eq!(cpu.mem.changes, [0x05]);
+
+    // only output pins differ, so nothing changes
+    cpu.set_port_input(0x02);
+    assert_eq!(cpu.port().pins(), 0x05);
+    assert_eq!(cpu.mem.changes, [0x05]);
+}
+
+#[test]
+fn other_variants_see_ram_at_0x0000_and_0x0001() {
+    for variant in [Variant::Nmos6502, Variant::Cmos65C02] {
+        let cpu = run(variant, &[LDA_IM, 0x2F, STA_ZP, 0x00, LDX_ZP, 0x00]);
+        assert_eq!(cpu.x, 0x2F, "{variant:?}");
+        assert_eq!(cpu.mem.mem.data[0x0000], 0x2F, "{variant:?}");
+        assert_eq!(cpu.mem.touched, [0x0000, 0x0000], "{variant:?}");
+        assert!(cpu.mem.changes.is_empty(), "{variant:?}");
+    }
+}
@@ -0,0 +1,90 @@
+//! regression suite for loading programs and multi-segment images
+
+use q_6502::{
+    bus::Bus,
+    cpu::{Cpu, RESET_VECTOR},
+    error::LoadError,
+    mem::Memory,
+};
+
+/// ram below 0x8000, rom that ignores writes above
+struct RomTop(Memory);
+
+impl Bus for RomTop {
+    fn read(&mut self, address: u16) -> u8 {
+        self.0.read(address)
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        if address < 0x8000 {
+            self.0.write(address, data)
+        }
+    }
+
+    fn peek(&self, address: u16) -> Option<u8> {
+        self.0.peek(address)
+    }
+}
+
+#[test]
+fn a_byte_fits_at_0xffff() {
+    let mut cpu = Cpu::new();
+    cpu.load_program(0xFFFF, &[0x42]).unwrap();
+    assert_eq!(cpu.mem.data[0xFFFF], 0x42);
+}
+
+#[test]
+fn a_program_running_past_0xffff_is_rejected() {
+    let mut cpu = Cpu::new();
+    let error = cpu.load_program(0xFFFF, &[0x42, 0x43]).unwrap_err();
+    assert_eq!(error, LoadError::OutOfBounds { address: 0xFFFF, len: 2 });
+    assert_eq!(cpu.mem.data[0xFFFF], 0x00);
+    assert_eq!(cpu.mem.data[0x0000], 0x00);
+}
+
+#[test]
+fn nothing_is_written_if_any_segment_is_out_of_bounds() {
+    let mut cpu = Cpu::new();
+    let error = cpu
+        .load_segments(&[(0x0200, &[0x01, 0x02]), (0xFFFE, &[0x03, 0x04, 0x05])], Some(0x0200))
+        .unwrap_err();
+    assert_eq!(error, LoadError::OutOfBounds { address: 0xFFFE, len: 3 });
+
+    assert_eq!(cpu.mem.data[0x0200..0x0202], [0x00, 0x00]);
+    assert_eq!(cpu.mem.data[0xFFFE..], [0x00, 0x00]);
+    assert_eq!(cpu.mem.data[0xFFFC..0xFFFE], [0x00, 0x00]);
+}
+
+#[test]
+fn entry_points_the_reset_vector_at_the_program() {
+    let mut cpu = Cpu::new();
+    cpu.load_segments(&[(0x8000, &[0xEA]), (0x9000, &[0xEA])], Some(0x8000))
+        .unwrap();
+    assert_eq!(cpu.mem.data[0x8000], 0xEA);
+    assert_eq!(cpu.mem.data[0x9000], 0xEA);
+    assert_eq!(cpu.mem.read_word(RESET_VECTOR as usize), 0x8000);
+
+    cpu.reset();
+    assert_eq!(cpu.pc, 0x8000);
+}
+
+#[test]
+fn no_entry_leaves_the_reset_vector_alone() {
+    let mut cpu = Cpu::new();
+    cpu.mem.write_word(RESET_VECTOR as usize, 0x1234);
+    cpu.load_segments(&[(0x8000, &[0xEA])], None).unwrap();
+    assert_eq!(cpu.mem.read_word(RESET_VECTOR as usize), 0x1234);
+}
+
+#[test]
+fn loading_into_rom_is_rejected() {
+    let mut cpu = Cpu::with_bus(RomTop(Memory::default()));
+    assert_eq!(
+        cpu.load_program(0x7FFF, &[0x01, 0x02]),
+        Err(LoadError::NotWritable { address: 0x8000 })
+    );
+    assert_eq!(cpu.mem.0.data[0x7FFF], 0x01);
+
+    let error = cpu.load_segments(&[(0x0200, &[0xEA])], Some(0x1234));
+    assert_eq!(error, Err(LoadError::NotWritable { address: RESET_VECTOR }));
+}
@@ -0,0 +1,99 @@
+//! regression suite for the wdc 65c02 WAI and STP instructions
+//! WAI sleeps until an interrupt arrives, STP until reset
+
+mod common;
+
+use common::{cpu_on, run_on, START};
+use q_6502::{
+    cpu::{Cpu, Step, NMI_VECTOR},
+    op_codes::*,
+    proc_stat::ProcStat,
+    variant::Variant,
+};
+
+const NMI_HANDLER: u16 = 0x0400;
+
+/// a wdc 65c02 that has just run the first instruction of program
+fn after_first(program: &[u8]) -> Cpu {
+    let mut cpu = cpu_on(Variant::Wdc65C02, program);
+    cpu.sp = 0xFF;
+    cpu.mem.write_word(NMI_VECTOR as usize, NMI_HANDLER);
+    cpu.mem.data[NMI_HANDLER as usize] = NOP;
+    cpu.step().unwrap();
+    cpu
+}
+
+#[test]
+fn wai_waits_until_an_interrupt_arrives() {
+    let mut cpu = after_first(&[WAI, INX]);
+    let cycles = cpu.cycles;
+    for _ in 0..3 {
+        assert_eq!(cpu.step(), Ok(Step::Waiting));
+    }
+    assert_eq!(cpu.pc, START + 1);
+    assert_eq!(cpu.x, 0);
+    assert_eq!(cpu.cycles, cycles);
+}
+
+#[test]
+fn masked_irq_resumes_after_wai_without_vectoring() {
+    let mut cpu = after_first(&[WAI, INX]);
+    assert!(cpu.p.contains(ProcStat::I));
+    cpu.assert_irq();
+
+    let step = cpu.step().unwrap();
+    assert!(matches!(step, Step::Instruction { pc, opcode: INX, .. } if pc == START + 1));
+    assert_eq!(cpu.x, 1);
+    assert_eq!(cpu.sp, 0xFF);
+}
+
+#[test]
+fn nmi_is_serviced_after_wai() {
+    let mut cpu = after_first(&[WAI, INX]);
+    cpu.assert_nmi();
+
+    let step = cpu.step().unwrap();
+    assert!(matches!(step, Step::Interrupt { vector: NMI_VECTOR, .. }));
+    assert_eq!(cpu.pc, NMI_HANDLER);
+    assert_eq!(cpu.mem.data[0x01FF], (START >> 8) as u8);
+    assert_eq!(cpu.mem.data[0x01FE], (START + 1) as u8);
+}
+
+#[test]
+fn stp_stops_until_reset() {
+    let mut cpu = after_first(&[STP, INX]);
+    assert_eq!(cpu.step(), Ok(Step::Stopped));
+
+    // interrupts do not wake it
+    cpu.assert_nmi();
+    cpu.assert_irq();
+    assert_eq!(cpu.step(), Ok(Step::Stopped));
+    assert_eq!(cpu.x, 0);
+
+    cpu.release_irq();
+    cpu.reset_to(START + 1);
+    let step = cpu.step().unwrap();
+    assert!(matches!(step, Step::Instruction { opcode: INX, .. }));
+    assert_eq!(cpu.x, 1);
+}
+
+#[test]
+fn run_for_cycles_and_run_until_return_early() {
+    for opcode in [WAI, STP] {
+        let mut cpu = cpu_on(Variant::Wdc65C02, &[opcode, INX]);
+        assert_eq!(cpu.run_for_cycles(100), Ok(3));
+        assert_eq!(cpu.pc, START + 1);
+
+        let mut cpu = cpu_on(Variant::Wdc65C02, &[opcode, INX]);
+        assert_eq!(cpu.run_until(|_| false), Ok(3));
+        assert_eq!(cpu.x, 0);
+    }
+}
+
+#[test]
+fn wai_and_stp_are_nops_before_the_wdc_part() {
+    for variant in [Variant::Cmos65C02, Variant::Rockwell65C02] {
+        let cpu = run_on(variant, &[WAI, STP, INX], |_| {}).unwrap();
+        assert_eq!(cpu.x, 1, "{variant:?}");
+    }
+}
@@ -0,0 +1,213 @@
+//! regression suite for the memory map address decoder
+
+use std::{cell::Cell, rc::Rc};
+
+use q_6502::{
+    bus::{BankSelection, Bus},
+    error::MapError,
+    map::MemoryMap,
+};
+
+/// a device that counts its reads and returns its offset
+struct Counter(Rc<Cell<u32>>);
+
+impl Bus for Counter {
+    fn read(&mut self, address: u16) -> u8 {
+        self.0.set(self.0.get() + 1);
+        address as u8
+    }
+
+    fn write(&mut self, _address: u16, _data: u8) {}
+}
+
+thread_local! {
+    /// the last write the rom hook saw
+    static ROM_WRITES: Cell<Option<(u16, u8)>> = const { Cell::new(None) };
+}
+
+fn record_rom_write(address: u16, data: u8) {
+    ROM_WRITES.with(|writes| writes.set(Some((address, data))));
+}
+
+/// the nes layout, 2K of ram mirrored to 0x1FFF and a 16K rom in the top half
+fn nes() -> MemoryMap {
+    let mut rom = vec![0xEA; 0x4000];
+    rom[0x3FFC] = 0x00;
+    rom[0x3FFD] = 0x80;
+    MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .mirror(0x0800..=0x1FFF, 0x0000..=0x07FF)
+        .rom(0x8000..=0xFFFF, rom)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn ram_is_mirrored_through_0x1fff() {
+    let mut map = nes();
+    map.write(0x0012, 0x34);
+    for mirror in [0x0812, 0x1012, 0x1812] {
+        assert_eq!(map.read(mirror), 0x34, "0x{mirror:04X}");
+    }
+
+    map.write(0x1FFF, 0x56);
+    assert_eq!(map.read(0x07FF), 0x56);
+}
+
+#[test]
+fn writes_to_rom_are_dropped_and_reported() {
+    let mut map = nes();
+    map.on_rom_write = Some(record_rom_write);
+
+    map.write(0xFFFC, 0x12);
+    assert_eq!(map.read(0xFFFC), 0x00);
+    assert_eq!(ROM_WRITES.with(Cell::get), Some((0xFFFC, 0x12)));
+}
+
+#[test]
+fn unmapped_reads_return_the_open_bus() {
+    let mut map = nes();
+    assert_eq!(map.read(0xFFFD), 0x80);
+    assert_eq!(map.read(0x5000), 0x80);
+
+    map.write(0x0000, 0x3C);
+    assert_eq!(map.read(0x6000), 0x3C);
+}
+
+#[test]
+fn overlapping_regions_are_rejected() {
+    let error = MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .rom(0x0400..=0x0FFF, [0x00])
+        .build()
+        .unwrap_err();
+    assert_eq!(error, MapError::Overlap { address: 0x0400 });
+}
+
+#[test]
+fn mirrors_of_unbacked_ranges_are_rejected() {
+    let error = MemoryMap::builder()
+        .ram(0x0000..=0x03FF)
+        .mirror(0x0800..=0x1FFF, 0x0000..=0x07FF)
+        .build()
+        .unwrap_err();
+    assert_eq!(error, MapError::MirrorTarget { start: 0x0800 });
+
+    let error = MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .mirror(0x0800..=0x0FFF, 0x0000..=0x07FF)
+        .mirror(0x1000..=0x1FFF, 0x0800..=0x0FFF)
+        .build()
+        .unwrap_err();
+    assert_eq!(error, MapError::MirrorTarget { start: 0x1000 });
+}
+
+#[test]
+fn mirrors_of_empty_ranges_are_rejected() {
+    #[allow(clippy::reversed_empty_ranges)]
+    let error = MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .mirror(0x0800..=0x1FFF, 0x0400..=0x03FF)
+        .build()
+        .unwrap_err();
+    assert_eq!(error, MapError::MirrorTarget { start: 0x0800 });
+}
+
+#[test]
+fn peek_does_not_read_devices() {
+    let reads = Rc::new(Cell::new(0));
+    let mut map = MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .rom(0xF000..=0xFFFF, [0x4C])
+        .device(0x2000..=0x2007, Counter(reads.clone()))
+        .build()
+        .unwrap();
+
+    map.write(0x0010, 0x42);
+    assert_eq!(map.peek(0x0010), Some(0x42));
+    assert_eq!(map.peek(0xFFFC), Some(0x4C));
+    assert_eq!(map.peek(0x2002), None);
+    assert_eq!(reads.get(), 0);
+
+    // an unmapped address shows the open bus value without changing it
+    assert_eq!(map.peek(0x5000), Some(0x42));
+}
+
+/// 2K of ram with a window at 0x8000 showing one of four 8K ram banks,
+/// selected by writing to 0x0100 in the middle of the ram
+fn banked() -> MemoryMap {
+    MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .banked_ram(0x8000..=0x9FFF, 4, 0x0100)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn writes_to_the_select_register_are_swallowed() {
+    let mut map = banked();
+    map.write(0x0100, 0x02);
+    assert_eq!(map.peek(0x0100), Some(0x00));
+}
+
+#[test]
+fn banks_hold_their_own_data() {
+    let mut map = banked();
+    map.write(0x8000, 0x11);
+
+    map.write(0x0100, 0x01);
+    assert_eq!(map.read(0x8000), 0x00);
+    map.write(0x8000, 0x22);
+
+    map.write(0x0100, 0x00);
+    assert_eq!(map.read(0x8000), 0x11);
+    map.write(0x0100, 0x01);
+    assert_eq!(map.read(0x8000), 0x22);
+}
+
+#[test]
+fn bank_numbers_wrap_around_the_bank_count() {
+    let mut map = banked();
+    map.write(0x0100, 0x01);
+    map.write(0x9FFF, 0x33);
+
+    map.write(0x0100, 0x05);
+    assert_eq!(map.read(0x9FFF), 0x33);
+    assert_eq!(map.bank_selection()[0].bank, 1);
+}
+
+#[test]
+fn bank_selection_reports_the_current_bank() {
+    let mut map = banked();
+    map.write(0x0100, 0x03);
+    assert_eq!(
+        map.bank_selection(),
+        [BankSelection {
+            start: 0x8000,
+            end: 0x9FFF,
+            bank: 3,
+            count: 4,
+        }]
+    );
+}
+
+#[test]
+fn windows_without_whole_banks_are_rejected() {
+    let error = MemoryMap::builder()
+        .banked_ram(0x8000..=0x9FFF, 0, 0x0100)
+        .build()
+        .unwrap_err();
+    assert_eq!(error, MapError::BankSize { start: 0x8000, len: 0 });
+
+    let error = MemoryMap::builder()
+        .banked_rom(0x8000..=0x8FFF, vec![0; 0x1800], 0x8000)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        MapError::BankSize {
+            start: 0x8000,
+            len: 0x1800,
+        }
+    );
+}
@@ -0,0 +1,19 @@
+//! regression suite for the opcode constants and the metadata table
+
+mod common;
+
+use common::run;
+use q_6502::{
+    op_codes::*,
+    op_table::{AddrMode, OPCODES},
+};
+
+#[test]
+fn ldx_absolute_is_0xae() {
+    let info = &OPCODES[LDX_ABS as usize];
+    assert_eq!(info.mnemonic, "LDX");
+    assert_eq!(info.mode, AddrMode::Absolute);
+
+    let cpu = run(&[LDX_ABS, 0x34, 0x12], |cpu| cpu.mem.data[0x1234] = 0x5A);
+    assert_eq!(cpu.x, 0x5A);
+}
@@ -0,0 +1,110 @@
+//! regression suite for running the cpu one bus cycle at a time
+
+mod common;
+
+use std::{cell::Cell, rc::Rc};
+
+use common::{cpu, START};
+use q_6502::{
+    bus::Bus,
+    cpu::{Access, Cpu},
+    map::MemoryMap,
+    op_codes::*,
+};
+
+/// a register that counts its reads, like a status register cleared by reading it
+struct Status(Rc<Cell<u32>>);
+
+impl Bus for Status {
+    fn read(&mut self, _address: u16) -> u8 {
+        self.0.set(self.0.get() + 1);
+        0x80
+    }
+
+    fn write(&mut self, _address: u16, _data: u8) {}
+}
+
+/// a cpu about to run LDA 0x4000, where a status register sits
+fn status() -> (Cpu<MemoryMap>, Rc<Cell<u32>>) {
+    let reads = Rc::new(Cell::new(0));
+    let map = MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .device(0x4000..=0x4000, Status(reads.clone()))
+        .build()
+        .unwrap();
+
+    let mut cpu = Cpu::with_bus(map);
+    cpu.reset_to(START);
+    cpu.load_program(START, &[LDA_ABS, 0x00, 0x40]).unwrap();
+    (cpu, reads)
+}
+
+fn read(address: u16, data: u8) -> Option<Access> {
+    Some(Access::Read { address, data })
+}
+
+fn write(address: u16, data: u8) -> Option<Access> {
+    Some(Access::Write { address, data })
+}
+
+#[test]
+fn each_tick_makes_one_access_on_the_bus() {
+    let (mut cpu, reads) = status();
+    let cycles = cpu.cycles;
+
+    assert_eq!(cpu.tick().unwrap(), read(START, LDA_ABS));
+    assert_eq!(cpu.tick().unwrap(), read(START + 1, 0x00));
+    assert_eq!(cpu.tick().unwrap(), read(START + 2, 0x40));
+    assert_eq!(reads.get(), 0);
+    assert_eq!(cpu.pc, START);
+    assert_eq!(cpu.a, 0x00);
+
+    assert_eq!(cpu.tick().unwrap(), read(0x4000, 0x80));
+    assert_eq!(reads.get(), 1);
+    assert_eq!(cpu.pc, START + 3);
+    assert_eq!(cpu.a, 0x80);
+    assert_eq!(cpu.cycles, cycles + 4);
+}
+
+#[test]
+fn read_modify_write_ticks_through_the_double_write() {
+    let mut cpu = cpu(&[INC_ZP, 0x10]);
+    cpu.mem.data[0x10] = 0x41;
+
+    assert_eq!(cpu.tick().unwrap(), read(START, INC_ZP));
+    assert_eq!(cpu.tick().unwrap(), read(START + 1, 0x10));
+    assert_eq!(cpu.tick().unwrap(), read(0x10, 0x41));
+    assert_eq!(cpu.tick().unwrap(), write(0x10, 0x41));
+    assert_eq!(cpu.mem.data[0x10], 0x41);
+    assert_eq!(cpu.tick().unwrap(), write(0x10, 0x42));
+    assert_eq!(cpu.mem.data[0x10], 0x42);
+}
+
+#[test]
+fn step_finishes_an_instruction_started_by_tick() {
+    let (mut cpu, reads) = status();
+    let cycles = cpu.cycles;
+    cpu.tick().unwrap();
+
+    assert_eq!(cpu.step().unwrap().cycles(), 4);
+    assert_eq!(reads.get(), 1);
+    assert_eq!(cpu.a, 0x80);
+    assert_eq!(cpu.cycles, cycles + 4);
+    assert_eq!(cpu.tick().unwrap(), read(START + 3, 0x00));
+}
+
+#[test]
+fn reset_abandons_the_instruction_being_ticked() {
+    let mut cpu = cpu(&[JSR, 0x00, 0x03]);
+    cpu.mem.data[0x0300] = NOP;
+    cpu.tick().unwrap();
+    cpu.tick().unwrap();
+
+    cpu.reset_to(0x0300);
+    assert_eq!(cpu.tick().unwrap(), read(0x0300, NOP));
+    assert_eq!(cpu.tick().unwrap(), read(0x0301, 0x00));
+
+    // the return address was never pushed
+    assert_eq!(cpu.mem.data[0x01FD], 0x00);
+    assert_eq!(cpu.mem.data[0x01FC], 0x00);
+}
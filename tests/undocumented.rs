@@ -0,0 +1,345 @@
+//! regression suite for the undocumented nmos opcodes
+//! under `IllegalOpcodePolicy::Execute` they behave like the real chip,
+//! `Trap` stops on them and `Nop` skips them
+
+mod common;
+
+use common::{cpu, run, START};
+use q_6502::{
+    cpu::Cpu,
+    error::{CpuError, IllegalOpcodePolicy},
+    op_codes::*,
+    proc_stat::ProcStat,
+};
+
+/// KIL, one of the opcodes that lock up the cpu
+const KIL: u8 = 0x02;
+
+/// run program at START with the undocumented opcodes executed
+fn exec(program: &[u8], setup: impl FnOnce(&mut Cpu)) -> Cpu {
+    run(program, |cpu| {
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Execute;
+        setup(cpu);
+    })
+}
+
+#[test]
+fn slo_shifts_memory_and_ors_it_into_a() {
+    let cpu = exec(&[SLO_ZP, 0x10], |cpu| {
+        cpu.a = 0x01;
+        cpu.mem.data[0x10] = 0x81;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x02);
+    assert_eq!(cpu.a, 0x03);
+    assert!(cpu.p.contains(ProcStat::C));
+    assert!(!cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::Z));
+}
+
+#[test]
+fn rla_rotates_memory_and_ands_it_into_a() {
+    let cpu = exec(&[RLA_ZP, 0x10], |cpu| {
+        cpu.a = 0x0F;
+        cpu.p.insert(ProcStat::C);
+        cpu.mem.data[0x10] = 0x81;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x03);
+    assert_eq!(cpu.a, 0x03);
+    assert!(cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn sre_shifts_memory_and_eors_it_into_a() {
+    let cpu = exec(&[SRE_ZP, 0x10], |cpu| {
+        cpu.a = 0xFF;
+        cpu.mem.data[0x10] = 0x03;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x01);
+    assert_eq!(cpu.a, 0xFE);
+    assert!(cpu.p.contains(ProcStat::C));
+    assert!(cpu.p.contains(ProcStat::N));
+}
+
+#[test]
+fn rra_adds_the_carry_shifted_out_by_the_rotate() {
+    let cpu = exec(&[RRA_ZP, 0x10], |cpu| {
+        cpu.a = 0x10;
+        cpu.mem.data[0x10] = 0x03;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x01);
+    assert_eq!(cpu.a, 0x12);
+    assert!(!cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn dcp_decrements_memory_and_compares() {
+    let cpu = exec(&[DCP_ZP, 0x10], |cpu| {
+        cpu.a = 0x40;
+        cpu.mem.data[0x10] = 0x41;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x40);
+    assert_eq!(cpu.a, 0x40);
+    assert!(cpu.p.contains(ProcStat::Z | ProcStat::C));
+}
+
+#[test]
+fn isc_increments_memory_and_subtracts() {
+    let cpu = exec(&[ISC_ZP, 0x10], |cpu| {
+        cpu.a = 0x20;
+        cpu.p.insert(ProcStat::C);
+        cpu.mem.data[0x10] = 0x0F;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x10);
+    assert_eq!(cpu.a, 0x10);
+    assert!(cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn sax_stores_a_and_x_without_touching_flags() {
+    let cpu = exec(&[SAX_ZP, 0x10, SAX_ZPY, 0x10], |cpu| {
+        cpu.a = 0xF0;
+        cpu.x = 0x0F;
+        cpu.y = 0x05;
+        cpu.mem.data[0x10] = 0xEE;
+        cpu.mem.data[0x15] = 0xEE;
+    });
+    assert_eq!(cpu.mem.data[0x10], 0x00);
+    assert_eq!(cpu.mem.data[0x15], 0x00);
+    assert!(!cpu.p.contains(ProcStat::Z));
+}
+
+#[test]
+fn lax_loads_a_and_x() {
+    let cpu = exec(&[LAX_ZP, 0x10], |cpu| cpu.mem.data[0x10] = 0x80);
+    assert_eq!(cpu.a, 0x80);
+    assert_eq!(cpu.x, 0x80);
+    assert!(cpu.p.contains(ProcStat::N));
+}
+
+#[test]
+fn lax_immediate_mixes_in_the_magic_constant() {
+    let cpu = exec(&[LAX_IM, 0xFF], |cpu| cpu.a = 0x01);
+    assert_eq!(cpu.a, 0xEF);
+    assert_eq!(cpu.x, 0xEF);
+    assert!(cpu.p.contains(ProcStat::N));
+}
+
+#[test]
+fn anc_copies_n_into_c() {
+    let cpu = exec(&[ANC_IM, 0xFF], |cpu| cpu.a = 0x80);
+    assert_eq!(cpu.a, 0x80);
+    assert!(cpu.p.contains(ProcStat::N | ProcStat::C));
+
+    let cpu = exec(&[ANC_IM_ALT, 0xFF], |cpu| {
+        cpu.a = 0x7F;
+        cpu.p.insert(ProcStat::C);
+    });
+    assert_eq!(cpu.a, 0x7F);
+    assert!(!cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn alr_ands_then_shifts_right() {
+    let cpu = exec(&[ALR_IM, 0x03], |cpu| cpu.a = 0xFF);
+    assert_eq!(cpu.a, 0x01);
+    assert!(cpu.p.contains(ProcStat::C));
+}
+
+#[test]
+fn arr_takes_c_and_v_from_bits_6_and_5() {
+    let cpu = exec(&[ARR_IM, 0xFF], |cpu| cpu.a = 0x80);
+    assert_eq!(cpu.a, 0x40);
+    assert!(cpu.p.contains(ProcStat::C | ProcStat::V));
+    assert!(!cpu.p.contains(ProcStat::N));
+
+    let cpu = exec(&[ARR_IM, 0xFF], |cpu| {
+        cpu.a = 0xFF;
+        cpu.p.insert(ProcStat::C);
+    });
+    assert_eq!(cpu.a, 0xFF);
+    assert!(cpu.p.contains(ProcStat::C | ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::V));
+}
+
+#[test]
+fn arr_applies_the_bcd_fixup_in_decimal_mode() {
+    let cpu = exec(&[ARR_IM, 0xFF], |cpu| {
+        cpu.a = 0xFF;
+        cpu.p.insert(ProcStat::D);
+    });
+    // N and Z come from the rotated value 0x7F, before either nibble is fixed
+    assert_eq!(cpu.a, 0xD5);
+    assert!(cpu.p.contains(ProcStat::C));
+    assert!(!cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::V));
+
+    let cpu = exec(&[ARR_IM, 0xFF], |cpu| {
+        cpu.a = 0x22;
+        cpu.p.insert(ProcStat::D | ProcStat::C);
+    });
+    assert_eq!(cpu.a, 0x91);
+    assert!(cpu.p.contains(ProcStat::N));
+    assert!(!cpu.p.contains(ProcStat::C | ProcStat::V));
+}
+
+#[test]
+fn xaa_mixes_in_the_magic_constant() {
+    let cpu = exec(&[XAA_IM, 0xFF], |cpu| {
+        cpu.a = 0x00;
+        cpu.x = 0xFF;
+    });
+    assert_eq!(cpu.a, 0xEE);
+    assert!(cpu.p.contains(ProcStat::N));
+}
+
+#[test]
+fn axs_subtracts_from_a_and_x_like_cmp() {
+    let cpu = exec(&[AXS_IM, 0x10], |cpu| {
+        cpu.a = 0xF0;
+        cpu.x = 0x3F;
+    });
+    assert_eq!(cpu.x, 0x20);
+    assert_eq!(cpu.a, 0xF0);
+    assert!(cpu.p.contains(ProcStat::C));
+
+    let cpu = exec(&[AXS_IM, 0x31], |cpu| {
+        cpu.a = 0xF0;
+        cpu.x = 0x3F;
+    });
+    assert_eq!(cpu.x, 0xFF);
+    assert!(!cpu.p.contains(ProcStat::C));
+    assert!(cpu.p.contains(ProcStat::N));
+}
+
+#[test]
+fn las_ands_memory_with_sp_into_a_x_and_sp() {
+    let cpu = exec(&[LAS_ABSY, 0x30, 0x12], |cpu| {
+        cpu.sp = 0xF0;
+        cpu.y = 0x04;
+        cpu.mem.data[0x1234] = 0x3C;
+    });
+    assert_eq!(cpu.a, 0x30);
+    assert_eq!(cpu.x, 0x30);
+    assert_eq!(cpu.sp, 0x30);
+}
+
+#[test]
+fn tas_sets_sp_and_stores_it_and_the_high_byte() {
+    let cpu = exec(&[TAS_ABSY, 0x00, 0x12], |cpu| {
+        cpu.a = 0xFF;
+        cpu.x = 0xF3;
+        cpu.y = 0x10;
+    });
+    assert_eq!(cpu.sp, 0xF3);
+    assert_eq!(cpu.mem.data[0x1210], 0x13);
+}
+
+#[test]
+fn shx_shy_and_ahx_store_the_register_and_high_byte() {
+    let cpu = exec(&[SHY_ABSX, 0x00, 0x12], |cpu| {
+        cpu.y = 0xFF;
+        cpu.x = 0x05;
+    });
+    assert_eq!(cpu.mem.data[0x1205], 0x13);
+
+    let cpu = exec(&[SHX_ABSY, 0x00, 0x12], |cpu| {
+        cpu.x = 0xFF;
+        cpu.y = 0x05;
+    });
+    assert_eq!(cpu.mem.data[0x1205], 0x13);
+
+    let cpu = exec(&[AHX_ABSY, 0x00, 0x12, AHX_ZPYI, 0x10], |cpu| {
+        cpu.a = 0xFF;
+        cpu.x = 0x0F;
+        cpu.y = 0x05;
+        cpu.mem.data[0x10] = 0x00;
+        cpu.mem.data[0x11] = 0x14;
+    });
+    assert_eq!(cpu.mem.data[0x1205], 0x03);
+    assert_eq!(cpu.mem.data[0x1405], 0x05);
+}
+
+#[test]
+fn shx_and_shy_corrupt_the_high_byte_on_a_page_cross() {
+    // 0x12FF + 2 crosses into 0x13, the stored value 0x0F & 0x13 becomes the high byte
+    let cpu = exec(&[SHY_ABSX, 0xFF, 0x12], |cpu| {
+        cpu.y = 0x0F;
+        cpu.x = 0x02;
+    });
+    assert_eq!(cpu.mem.data[0x0301], 0x03);
+    assert_eq!(cpu.mem.data[0x1301], 0x00);
+
+    let cpu = exec(&[SHX_ABSY, 0xFF, 0x12], |cpu| {
+        cpu.x = 0x0F;
+        cpu.y = 0x02;
+    });
+    assert_eq!(cpu.mem.data[0x0301], 0x03);
+    assert_eq!(cpu.mem.data[0x1301], 0x00);
+}
+
+#[test]
+fn undocumented_nops_skip_their_operands() {
+    let cpu = exec(&[0x80, 0xFF, 0x0C, 0x34, 0x12, 0x1A], |cpu| cpu.a = 0x42);
+    assert_eq!(cpu.a, 0x42);
+}
+
+#[test]
+fn sbc_alternate_subtracts() {
+    let cpu = exec(&[SBC_IM_ALT, 0x01], |cpu| {
+        cpu.a = 0x10;
+        cpu.p.insert(ProcStat::C);
+    });
+    assert_eq!(cpu.a, 0x0F);
+}
+
+#[test]
+fn kil_jams_the_cpu_until_reset() {
+    let mut cpu = cpu(&[KIL, NOP]);
+    cpu.illegal_opcodes = IllegalOpcodePolicy::Execute;
+    let jammed = CpuError::Jammed {
+        pc: START,
+        opcode: KIL,
+    };
+    assert_eq!(cpu.step(), Err(jammed));
+    assert_eq!(cpu.step(), Err(jammed));
+    assert_eq!(cpu.pc, START);
+
+    // an interrupt does not wake it either
+    cpu.assert_nmi();
+    assert_eq!(cpu.step(), Err(jammed));
+
+    cpu.reset_to(START + 1);
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, START + 2);
+}
+
+#[test]
+fn trap_stops_on_the_opcode() {
+    let mut cpu = cpu(&[SLO_ZP, 0x10, KIL]);
+    assert_eq!(
+        cpu.step(),
+        Err(CpuError::UnknownOpcode {
+            pc: START,
+            opcode: SLO_ZP,
+        })
+    );
+    assert_eq!(cpu.pc, START);
+    assert_eq!(cpu.mem.data[0x10], 0x00);
+
+    // the cpu is not jammed, the same opcode runs once the policy allows it
+    cpu.illegal_opcodes = IllegalOpcodePolicy::Nop;
+    cpu.step().unwrap();
+    assert_eq!(cpu.pc, START + 2);
+}
+
+#[test]
+fn nop_skips_the_opcode_and_its_operand() {
+    let cpu = run(&[SLO_ABS, 0x34, 0x12, KIL, LAX_IM, 0xFF], |cpu| {
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Nop;
+        cpu.a = 0x01;
+        cpu.mem.data[0x1234] = 0x81;
+    });
+    assert_eq!(cpu.a, 0x01);
+    assert_eq!(cpu.x, 0x00);
+    assert_eq!(cpu.mem.data[0x1234], 0x81);
+}
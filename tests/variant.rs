@@ -0,0 +1,62 @@
+//! regression suite for the differences between members of the 6502 family
+
+mod common;
+
+use common::{cpu_on, run_on, START};
+use q_6502::{
+    cpu::Cpu,
+    error::{CpuError, IllegalOpcodePolicy},
+    mem::Memory,
+    proc_stat::ProcStat,
+    variant::Variant,
+};
+
+#[test]
+fn default_cpu_is_an_nmos_6502() {
+    let cpu: Cpu<Memory> = Cpu::default();
+    assert_eq!(cpu.variant(), Variant::Nmos6502);
+    assert!(cpu.jmp_indirect_bug);
+}
+
+#[test]
+fn cmos_reserved_opcodes_are_nops_under_every_policy() {
+    // a two byte, another two byte and a single byte reserved opcode
+    let program = [0x02, 0xFF, 0x44, 0x10, 0xCB];
+    for policy in [
+        IllegalOpcodePolicy::Trap,
+        IllegalOpcodePolicy::Nop,
+        IllegalOpcodePolicy::Execute,
+    ] {
+        let cpu = run_on(Variant::Cmos65C02, &program, |cpu| cpu.illegal_opcodes = policy).unwrap();
+        assert_eq!(cpu.pc, START + 5, "{policy:?}");
+        assert_eq!(cpu.a, 0x00, "{policy:?}");
+    }
+}
+
+#[test]
+fn nmos_illegal_opcodes_follow_the_policy() {
+    let error = run_on(Variant::Nmos6502, &[0x02], |cpu| {
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Trap;
+    })
+    .unwrap_err();
+    assert_eq!(error, CpuError::UnknownOpcode { pc: START, opcode: 0x02 });
+
+    let error = run_on(Variant::Nmos6502, &[0x02], |cpu| {
+        cpu.illegal_opcodes = IllegalOpcodePolicy::Execute;
+    })
+    .unwrap_err();
+    assert_eq!(error, CpuError::Jammed { pc: START, opcode: 0x02 });
+}
+
+#[test]
+fn reset_clears_decimal_mode_on_the_65c02_only() {
+    let mut cpu = cpu_on(Variant::Cmos65C02, &[]);
+    cpu.p.insert(ProcStat::D);
+    cpu.reset();
+    assert!(!cpu.p.contains(ProcStat::D));
+
+    let mut cpu = cpu_on(Variant::Nmos6502, &[]);
+    cpu.p.insert(ProcStat::D);
+    cpu.reset();
+    assert!(cpu.p.contains(ProcStat::D));
+}